@@ -0,0 +1,55 @@
+//! Generates a registry of all solutions in `src/bin`, which the `in_process` feature links into the main binary.
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solutions.rs");
+
+    // NOTE: solutions are named `<year>-<day>.rs`, e.g. `2024-01.rs`.
+    let mut puzzles: Vec<((u16, u8), PathBuf)> = fs::read_dir(manifest_dir.join("src").join("bin"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let is_solution = path.extension()? == "rs" && stem.len() == 7;
+                    let (year, day) = stem.split_once('-').filter(|_| is_solution)?;
+                    let year = year.parse::<u16>().ok().filter(|x| *x >= 2015)?;
+                    let day = day.parse::<u8>().ok()?;
+                    (1..=25).contains(&day).then_some(((year, day), path))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    let mut out = String::new();
+
+    for ((year, day), path) in &puzzles {
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(out, "mod year_{year}_day_{day:02};").unwrap();
+    }
+
+    writeln!(
+        out,
+        "\npub fn solutions() -> Vec<(advent_of_code::template::PuzzleId, advent_of_code::template::in_process::SolutionFn)> {{"
+    )
+    .unwrap();
+    writeln!(out, "    vec![").unwrap();
+    for ((year, day), _) in &puzzles {
+        writeln!(
+            out,
+            "        (advent_of_code::template::PuzzleId::new(advent_of_code::year!({year}), advent_of_code::day!({day})), year_{year}_day_{day:02}::__run),"
+        )
+        .unwrap();
+    }
+    writeln!(out, "    ]").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    fs::write(out_path, out).unwrap();
+}
@@ -36,20 +36,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(a.iter().map(|x| x * counts.get(x).unwrap_or(&0)).sum())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
@@ -2,9 +2,11 @@ use std::collections::HashSet;
 
 use advent_of_code::{Matrix, Point, CARDINALS};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, parse);
 
-fn parse(input: &str) -> (Matrix<u32>, Vec<Point>) {
+type Input = (Matrix<u32>, Vec<Point>);
+
+fn parse(input: &str) -> Input {
     let mut trailheads = vec![];
 
     let cells: Vec<Vec<u32>> = input
@@ -60,39 +62,20 @@ fn map_path(matrix: &Matrix<u32>, paths: Vec<Point>) -> Vec<Point> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (matrix, trailheads) = parse(input);
+pub fn part_one((matrix, trailheads): &Input) -> Option<usize> {
     Some(
         trailheads
-            .into_iter()
-            .map(|trailhead| HashSet::<Point>::from_iter(map_path(&matrix, vec![trailhead])).len())
+            .iter()
+            .map(|trailhead| HashSet::<Point>::from_iter(map_path(matrix, vec![*trailhead])).len())
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (matrix, trailheads) = parse(input);
+pub fn part_two((matrix, trailheads): &Input) -> Option<usize> {
     Some(
         trailheads
-            .into_iter()
-            .map(|trailhead| map_path(&matrix, vec![trailhead]).len())
+            .iter()
+            .map(|trailhead| map_path(matrix, vec![*trailhead]).len())
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
-}
@@ -24,7 +24,7 @@ fn blink(stone: u64, times: u64) -> u64 {
 
     let digits = (stone as f32).log10().floor() as u32 + 1;
 
-    if digits % 2 == 0 {
+    if digits.is_multiple_of(2) {
         let n = 10_u64.pow(digits / 2);
         return blink(stone / n, times - 1) + blink(stone % n, times - 1);
     }
@@ -41,14 +41,3 @@ pub fn part_two(input: &str) -> Option<u64> {
     BLINK_CACHE.lock().unwrap().cache_reset();
     Some(parse(input).into_iter().map(|x| blink(x, 75)).sum())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
-}
@@ -93,14 +93,3 @@ pub fn part_two(input: &str) -> Option<i64> {
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
-    }
-}
@@ -1,40 +1,92 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answers, compare, dev, download, examples, read, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
+use advent_of_code::template::{EventCalendar, PuzzleId};
 use std::process;
 
+#[cfg(feature = "in_process")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        BenchConfig, Day, DaySelection, PuzzleId, RegressionConfig, Statistic, TableFormat,
+        TimeoutConfig, Year,
+    };
+    use std::{num::NonZeroUsize, process};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+            /// Download files again, even if they exist or are cached.
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            /// Retry a rate limited submission once the rate limit has passed.
+            wait: bool,
+            /// Read the input from this file instead of `data/<year>/inputs`.
+            input: Option<String>,
+            bench_config: Option<BenchConfig>,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            release: bool,
+        },
+        ExtractExamples {
+            puzzle: PuzzleId,
+            /// 1-based index of the code block to use as example.
+            block: Option<usize>,
+            list: bool,
+            overwrite: bool,
+        },
+        Dev {
+            puzzle: PuzzleId,
+            release: bool,
+            timeouts: TimeoutConfig,
         },
         All {
+            year: Year,
+            selection: DaySelection,
             release: bool,
+            jobs: NonZeroUsize,
+            timeouts: TimeoutConfig,
         },
         Time {
+            year: Year,
             all: bool,
-            day: Option<Day>,
+            selection: DaySelection,
             store: bool,
+            statistic: Statistic,
+            bench_config: BenchConfig,
+            timeouts: TimeoutConfig,
+            regressions: RegressionConfig,
+            memory: bool,
+        },
+        Answers {
+            year: Year,
+            set: Option<(Day, u8, String)>,
+        },
+        Compare {
+            year: Year,
+            old: String,
+            new: Option<String>,
+            statistic: Statistic,
+            format: TableFormat,
         },
         #[cfg(feature = "today")]
         Today,
@@ -43,36 +95,133 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let subcommand = args.subcommand()?;
+
+        // NOTE: parse the year before any free arguments, so it can be passed anywhere after the subcommand.
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        let year = year.or_else(Year::from_env);
+        let year = || {
+            year.ok_or(
+                "no event year set. Pass `--year <year>` or set the AOC_YEAR environment variable.",
+            )
+        };
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let release = args.contains("--release");
+                let jobs = args
+                    .opt_value_from_str(["-j", "--jobs"])?
+                    .unwrap_or(NonZeroUsize::MIN);
+                let timeouts = TimeoutConfig::from_args(&mut args)?;
+
+                AppArguments::All {
+                    year: year()?,
+                    selection: DaySelection::from_args(&mut args)?,
+                    release,
+                    jobs,
+                    timeouts,
+                }
+            }
+            Some("time") if args.contains("--compare") => AppArguments::Compare {
+                year: year()?,
+                statistic: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+                format: if args.contains("--markdown") {
+                    TableFormat::Markdown
+                } else {
+                    TableFormat::Terminal
+                },
+                new: args.opt_value_from_str("--to")?,
+                old: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let statistic = args.opt_value_from_str("--stat")?.unwrap_or_default();
+                let bench_config = BenchConfig::from_args(&mut args)?;
+                let timeouts = TimeoutConfig::from_args(&mut args)?;
+                let regressions = RegressionConfig::from_args(&mut args)?;
 
                 AppArguments::Time {
+                    year: year()?,
                     all,
-                    day: args.opt_free_from_str()?,
+                    selection: DaySelection::from_args(&mut args)?,
                     store,
+                    statistic,
+                    bench_config,
+                    timeouts,
+                    regressions,
+                    memory,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                force: args.contains("--force"),
+                puzzle: PuzzleId::new(year()?, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year()?, args.free_from_str()?),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year()?, args.free_from_str()?),
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let wait = args.contains("--wait");
+                let dhat = args.contains("--dhat");
+                let time = args.contains("--time");
+                let input = args.opt_value_from_str("--input")?;
+                let bench_config = BenchConfig::from_args(&mut args)?;
+
+                AppArguments::Solve {
+                    puzzle: PuzzleId::new(year()?, args.free_from_str()?),
+                    release,
+                    dhat,
+                    submit,
+                    wait,
+                    input,
+                    bench_config: time.then_some(bench_config),
+                }
+            }
+            Some("examples") if args.contains("--extract") => AppArguments::ExtractExamples {
+                block: args.opt_value_from_str("--block")?,
+                list: args.contains("--list"),
+                overwrite: args.contains("--overwrite"),
+                puzzle: PuzzleId::new(year()?, args.free_from_str()?),
+            },
+            Some("examples") => AppArguments::Examples {
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                puzzle: PuzzleId::new(year()?, args.free_from_str()?),
+            },
+            Some("dev") => {
+                let release = args.contains("--release");
+                let timeouts = TimeoutConfig::from_args(&mut args)?;
+
+                AppArguments::Dev {
+                    puzzle: PuzzleId::new(year()?, args.free_from_str()?),
+                    release,
+                    timeouts,
+                }
+            }
+            Some("answers") => match args.subcommand()?.as_deref() {
+                Some("set") => AppArguments::Answers {
+                    year: year()?,
+                    set: Some((
+                        args.free_from_str()?,
+                        args.free_from_str()?,
+                        args.free_from_str()?,
+                    )),
+                },
+                Some(x) => {
+                    eprintln!("Unknown answers command: {x}");
+                    process::exit(1);
+                }
+                None => AppArguments::Answers {
+                    year: year()?,
+                    set: None,
+                },
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -96,44 +245,115 @@ mod args {
 }
 
 fn main() {
+    #[cfg(feature = "in_process")]
+    advent_of_code::template::in_process::register(solutions::solutions());
+
+    // NOTE: the event calendars decide which days exist and which day has a single part.
+    if let Err(e) = EventCalendar::check_env() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                selection,
+                release,
+                jobs,
+                timeouts,
+            } => all::handle(year, &selection, release, jobs.get(), &timeouts),
+            AppArguments::Examples { puzzle, release } => examples::handle(puzzle, release),
+            AppArguments::ExtractExamples {
+                puzzle,
+                block,
+                list,
+                overwrite,
+            } => examples::handle_extract(puzzle, block, list, overwrite),
+            AppArguments::Dev {
+                puzzle,
+                release,
+                timeouts,
+            } => dev::handle(puzzle, release, &timeouts),
+            AppArguments::Time {
+                year,
+                selection,
+                all,
+                store,
+                statistic,
+                bench_config,
+                timeouts,
+                regressions,
+                memory,
+            } => time::handle(
+                year,
+                &selection,
+                all,
+                store,
+                statistic,
+                &bench_config,
+                &timeouts,
+                &regressions,
+                memory,
+            ),
+            AppArguments::Download { puzzle, force } => download::handle(puzzle, force),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
             AppArguments::Scaffold {
-                day,
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle, false);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                wait,
+                input,
+                bench_config,
+            } => solve::handle(
+                puzzle,
+                release,
+                dhat,
+                submit,
+                wait,
+                input.as_deref(),
+                bench_config.as_ref(),
+            ),
+            AppArguments::Compare {
+                year,
+                old,
+                new,
+                statistic,
+                format,
+            } => compare::handle(year, &old, new.as_deref(), statistic, format),
+            AppArguments::Answers { year, set } => match set {
+                Some((day, part, answer)) => {
+                    answers::handle_set(PuzzleId::new(year, day), part, &answer);
+                }
+                None => answers::handle_list(year),
+            },
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle, false);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during an event. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents a confirmed answer for a part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+}
+
+/// Represents the confirmed answers of all days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(ANSWERS_FILE_NAME)
+    }
+
+    /// Dehydrate the answers of a year to its JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(year.data_dir())?;
+        let mut file = fs::File::create(Answers::file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the answers of a year from its JSON file. If not present, returns empty answers.
+    /// A file that can not be read or parsed is an error, so that it is not overwritten by a later store.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = Answers::file_path(year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Answers::try_from(s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Get the confirmed answer for a part of a day.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day && a.part == part)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Set the confirmed answer for a part of a day, replacing an existing answer.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        self.data.retain(|a| !(a.day == day && a.part == part));
+        self.data.push(Answer {
+            day,
+            part,
+            answer: answer.into(),
+        });
+        self.data.sort_unstable_by_key(|a| (a.day, a.part));
+    }
+
+    /// Check an answer against the registry.
+    /// Returns the confirmed answer if it differs from `answer`, `None` if it matches or is unknown.
+    pub fn find_mismatch(&self, day: Day, part: u8, answer: Option<&str>) -> Option<&str> {
+        self.get(day, part)
+            .filter(|expected| answer != Some(*expected))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .filter(|x| *x == 1 || *x == 2)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.answer to be a string.")?;
+
+        Ok(Answer {
+            day,
+            part,
+            answer: answer.clone(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::Answers;
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.set(day!(6), 2, "1812");
+        answers.set(day!(1), 1, "42");
+        answers
+    }
+
+    mod deserialization {
+        use crate::{day, template::answers::Answers};
+
+        #[test]
+        fn handles_json_answers() {
+            let json = r#"{ "data": [{ "day": "01", "part": 2, "answer": "42" }] }"#.to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            assert_eq!(answers.get(day!(1), 2), Some("42"));
+            assert_eq!(answers.get(day!(1), 1), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_invalid_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part": 3, "answer": "42" }] }"#.to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    mod serialization {
+        use super::get_mock_answers;
+        use crate::{day, template::answers::Answers};
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn roundtrips_answers() {
+            let answers = get_mock_answers();
+            let json = JsonValue::from(answers).stringify().unwrap();
+            let parsed = Answers::try_from(json).unwrap();
+            assert_eq!(parsed.get(day!(6), 2), Some("1812"));
+            assert_eq!(parsed.data[0].day, day!(1));
+        }
+    }
+
+    mod registry {
+        use super::get_mock_answers;
+        use crate::day;
+
+        #[test]
+        fn replaces_answers() {
+            let mut answers = get_mock_answers();
+            answers.set(day!(6), 2, "1813");
+            assert_eq!(answers.data.len(), 2);
+            assert_eq!(answers.get(day!(6), 2), Some("1813"));
+        }
+
+        #[test]
+        fn finds_mismatches() {
+            let answers = get_mock_answers();
+            assert_eq!(answers.find_mismatch(day!(1), 1, Some("42")), None);
+            assert_eq!(answers.find_mismatch(day!(1), 1, Some("43")), Some("42"));
+            assert_eq!(answers.find_mismatch(day!(1), 1, None), Some("42"));
+            assert_eq!(answers.find_mismatch(day!(1), 2, Some("1")), None);
+        }
+    }
+}
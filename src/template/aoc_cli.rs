@@ -1,125 +1,133 @@
-/// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
-
-use crate::template::Day;
-
-#[derive(Debug)]
-pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-}
+/// Wrapper module around the Advent of Code website, see [`aoc_client`](crate::template::aoc_client).
+use std::{fs, path::Path};
 
-impl Display for AocCommandError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
-            }
-        }
-    }
-}
+use crate::template::aoc_client::{is_login_page, Client};
+use crate::template::cache::Cache;
+use crate::template::PuzzleId;
 
-pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+pub use crate::template::aoc_client::{AocClientError, SubmissionVerdict};
+
+/// Check that a session cookie is configured, without contacting the server.
+pub fn check() -> Result<(), AocClientError> {
+    crate::template::aoc_client::find_session().map(|_| ())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// Download the puzzle description of a day to its puzzle file and print it.
+pub fn read(puzzle: PuzzleId) -> Result<String, AocClientError> {
+    let client = Client::from_env()?;
+    let puzzle_path = puzzle.puzzle_path();
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+    let description = client.get_puzzle(puzzle)?;
+    write_file(&puzzle_path, &description)?;
+    store_in_cache(Cache::from_env().as_ref(), &puzzle_path, &description);
 
-    call_aoc_cli(&args)
+    println!("{description}");
+    Ok(description)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
+/// Make the input and the puzzle description of a day available. Existing files are kept, missing files are
+/// restored from the local cache or downloaded. With `force`, both files are downloaded again.
+pub fn download(puzzle: PuzzleId, force: bool) -> Result<(), AocClientError> {
+    let cache = Cache::from_env();
+    let mut client = None;
+
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
-}
+    fetch_file(
+        &puzzle.input_path(),
+        "input",
+        force,
+        cache.as_ref(),
+        &mut client,
+        |client| client.get_input(puzzle),
+    )?;
+
+    fetch_file(
+        &puzzle.puzzle_path(),
+        "puzzle",
+        force,
+        cache.as_ref(),
+        &mut client,
+        |client| client.get_puzzle(puzzle),
+    )?;
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    Ok(())
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Whether a file holds downloaded content, as opposed to being empty or an error page.
+fn is_downloaded(contents: &str) -> bool {
+    !contents.trim().is_empty() && !is_login_page(contents)
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+/// Keep the file at `path`, restore it from the cache or download it with `get`.
+/// The client is only created when needed, so that restoring files works without a session cookie.
+fn fetch_file(
+    path: &Path,
+    label: &str,
+    force: bool,
+    cache: Option<&Cache>,
+    client: &mut Option<Client>,
+    get: impl FnOnce(&Client) -> Result<String, AocClientError>,
+) -> Result<(), AocClientError> {
+    if !force {
+        if fs::read_to_string(path).is_ok_and(|x| is_downloaded(&x)) {
+            println!(
+                "🎄 Kept existing {label} \"{}\". Pass `--force` to download it again.",
+                path.display()
+            );
+            return Ok(());
+        }
+
+        if let Some(contents) = cache
+            .and_then(|x| x.read(path))
+            .filter(|x| is_downloaded(x))
+        {
+            write_file(path, &contents)?;
+            println!(
+                "🎄 Restored {label} to \"{}\" from the cache.",
+                path.display()
+            );
+            return Ok(());
+        }
     }
-}
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+    let client = match client {
+        Some(client) => client,
+        None => client.insert(Client::from_env()?),
+    };
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
+    let contents = get(client)?;
+    write_file(path, &contents)?;
+    store_in_cache(cache, path, &contents);
+
+    println!("🎄 Successfully wrote {label} to \"{}\".", path.display());
+    Ok(())
+}
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+/// Keep a copy of a downloaded file in the cache. Failing to do so only warrants a warning.
+fn store_in_cache(cache: Option<&Cache>, path: &Path, contents: &str) {
+    if let Some(Err(e)) = cache.map(|x| x.store(path, contents)) {
+        eprintln!("Failed to store \"{}\" in the cache: {e}", path.display());
+    }
+}
 
-    cmd_args
+/// Submit an answer. The message of the server is printed, its verdict is returned.
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionVerdict, AocClientError> {
+    let client = Client::from_env()?;
+    let message = client.submit(puzzle, part, result)?;
+    println!("{message}");
+    Ok(SubmissionVerdict::parse(&message))
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
-
-    if output.status.success() {
-        Ok(output)
-    } else {
-        Err(AocCommandError::BadExitStatus(output))
+/// Write a file, creating the data folders of its year if needed.
+fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
     }
+    fs::write(path, contents)?;
+    Ok(())
 }
@@ -0,0 +1,644 @@
+/// Minimal client for the Advent of Code website: downloads inputs and puzzle descriptions and submits answers.
+/// Authenticates with the `session` cookie of a logged in browser.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::PuzzleId;
+
+static BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable with the session cookie. Takes precedence over the session file.
+pub static SESSION_ENV_VAR: &str = "ADVENT_OF_CODE_SESSION";
+
+/// Name of the file in the home directory that holds the session cookie.
+pub static SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+/// Identifies the template to the maintainers of Advent of Code, as they request of automated tools.
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    /// The server asked to log in, because the session cookie is invalid or expired.
+    NotLoggedIn,
+    /// The server responded with an error status, e.g. because the puzzle is not unlocked yet.
+    BadStatus(u16),
+    Request(String),
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set the {SESSION_ENV_VAR} environment variable or create the file \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::NotLoggedIn => write!(
+                f,
+                "the server asked to log in. The session cookie is invalid or expired, update it in the {SESSION_ENV_VAR} environment variable or \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(value: io::Error) -> Self {
+        AocClientError::Io(value)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(value: ureq::Error) -> Self {
+        match value {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Request(e.to_string()),
+        }
+    }
+}
+
+/// The verdict of the server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionVerdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// The part was solved before, or is not unlocked yet.
+    AlreadySolved,
+    /// An answer was submitted too recently. Holds the time left to wait, if the server reported it.
+    RateLimited(Option<Duration>),
+    /// The response could not be interpreted.
+    Unknown,
+}
+
+impl SubmissionVerdict {
+    /// Interpret the message the server responds with to a submission.
+    pub fn parse(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            SubmissionVerdict::Correct
+        } else if message.contains("your answer is too high") {
+            SubmissionVerdict::TooHigh
+        } else if message.contains("your answer is too low") {
+            SubmissionVerdict::TooLow
+        } else if message.contains("That's not the right answer") {
+            SubmissionVerdict::Incorrect
+        } else if message.contains("You gave an answer too recently") {
+            SubmissionVerdict::RateLimited(parse_wait(message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmissionVerdict::AlreadySolved
+        } else {
+            SubmissionVerdict::Unknown
+        }
+    }
+}
+
+impl SubmissionVerdict {
+    /// Whether the server rejected the answer.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            SubmissionVerdict::TooHigh | SubmissionVerdict::TooLow | SubmissionVerdict::Incorrect
+        )
+    }
+}
+
+impl Display for SubmissionVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionVerdict::Correct => write!(f, "correct"),
+            SubmissionVerdict::TooHigh => write!(f, "too high"),
+            SubmissionVerdict::TooLow => write!(f, "too low"),
+            SubmissionVerdict::Incorrect => write!(f, "incorrect"),
+            SubmissionVerdict::AlreadySolved => write!(f, "already solved"),
+            SubmissionVerdict::RateLimited(Some(wait)) => {
+                write!(f, "rate limited for {}s", wait.as_secs())
+            }
+            SubmissionVerdict::RateLimited(None) => write!(f, "rate limited"),
+            SubmissionVerdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Parse the time left to wait from a rate limit message, e.g. "You have 1m 5s left to wait."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|x| {
+            let unit = match x.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            x[..x.len() - 1].parse::<u64>().ok().map(|x| x * unit)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Read the session cookie from the environment, falling back to the session file in the home directory.
+pub fn find_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var(SESSION_ENV_VAR) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .ok_or(AocClientError::SessionNotFound)?;
+
+    match fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        _ => Err(AocClientError::SessionNotFound),
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .timeout(TIMEOUT)
+                .user_agent(USER_AGENT)
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Create a client for the Advent of Code website with the session cookie of the user.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        Ok(Client::new(BASE_URL, &find_session()?))
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        match self.agent.get(url).set("Cookie", &self.cookie()).call() {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                if is_login_page(&body) {
+                    Err(AocClientError::NotLoggedIn)
+                } else {
+                    Err(AocClientError::BadStatus(status))
+                }
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let input = self.get(&format!("{}/input", self.day_url(puzzle)))?;
+
+        // NOTE: make sure an error page is never stored as input.
+        if is_login_page(&input) {
+            return Err(AocClientError::NotLoggedIn);
+        }
+
+        Ok(input)
+    }
+
+    /// Get the description of a puzzle as markdown.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(puzzle))?;
+        Ok(puzzle_to_markdown(&html))
+    }
+
+    /// Submit an answer and return the message of the response.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        let article = find_element(&html, "<article", "</article>").unwrap_or(&html);
+
+        Ok(html_to_markdown(article).trim().to_string())
+    }
+}
+
+/// Whether a response is the page that asks to log in, e.g. "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+pub fn is_login_page(body: &str) -> bool {
+    body.contains("Please log in")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Find the first element that starts with `open` and ends with `close`.
+fn find_element<'a>(html: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = html.find(open)?;
+    let end = start + html[start..].find(close)? + close.len();
+    Some(&html[start..end])
+}
+
+/// Convert the description articles of a puzzle page to markdown.
+/// The answers of solved parts follow their article, so they are kept as well.
+fn puzzle_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+
+    for chunk in html.split("<article class=\"day-desc\">").skip(1) {
+        let Some((article, rest)) = chunk.split_once("</article>") else {
+            continue;
+        };
+
+        markdown.push_str(&html_to_markdown(article));
+
+        if rest.trim_start().starts_with("<p>Your puzzle answer was") {
+            if let Some(answer) = find_element(rest, "<p>", "</p>") {
+                markdown.push_str(&html_to_markdown(answer));
+            }
+        }
+    }
+
+    markdown
+}
+
+/// Convert the subset of HTML that Advent of Code uses to markdown.
+/// Code blocks become fenced blocks and emphasis inside code is kept, e.g. `<code><em>11</em></code>` becomes `` `*11*` ``.
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut markdown, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut markdown, &rest[..start], in_pre);
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match name.as_str() {
+            "pre" => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            "code" | "/code" if !in_pre => markdown.push('`'),
+            // NOTE: code blocks are copied verbatim into example inputs, so they must not contain markers.
+            "em" | "/em" if !in_pre => markdown.push('*'),
+            "h2" => markdown.push_str("## "),
+            "li" => markdown.push_str("- "),
+            "/h2" | "/p" => markdown.push_str("\n\n"),
+            "/li" | "/ul" => markdown.push('\n'),
+            _ => {}
+        }
+    }
+
+    markdown
+}
+
+/// Append text to markdown, collapsing whitespace outside of code blocks.
+fn push_text(markdown: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        markdown.push_str(&text);
+        return;
+    }
+
+    for (index, word) in text.split_whitespace().enumerate() {
+        let needs_space = index > 0
+            || (text.starts_with(char::is_whitespace)
+                && !markdown.is_empty()
+                && !markdown.ends_with(char::is_whitespace));
+        if needs_space {
+            markdown.push(' ');
+        }
+        markdown.push_str(word);
+    }
+
+    if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+        markdown.push(' ');
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let character = entity.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => name
+                .strip_prefix("#x")
+                .and_then(|x| u32::from_str_radix(x, 16).ok())
+                .or_else(|| name.strip_prefix('#').and_then(|x| x.parse().ok()))
+                .and_then(char::from_u32),
+        });
+
+        match (character, entity) {
+            (Some(character), Some((_, end))) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use crate::{
+        day,
+        template::{puzzle::Puzzle, PuzzleId},
+        year,
+    };
+
+    use super::{
+        decode_entities, html_to_markdown, puzzle_to_markdown, AocClientError, Client,
+        SubmissionVerdict,
+    };
+
+    /// A request received by the stand-in server.
+    struct Request {
+        head: String,
+        body: String,
+    }
+
+    /// Serve a single canned response on a local port, standing in for the Advent of Code website.
+    /// Returns the base url of the server and a receiver for the request it got.
+    fn serve(status: &str, body: &str) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+
+            let length = head
+                .lines()
+                .find_map(|x| {
+                    x.to_lowercase()
+                        .strip_prefix("content-length:")?
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            stream.write_all(response.as_bytes()).unwrap();
+            tx.send(Request {
+                head,
+                body: String::from_utf8(body).unwrap(),
+            })
+            .unwrap();
+        });
+
+        (url, rx)
+    }
+
+    const PUZZLE_HTML: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>In the example above, this is <code>2 + 1 = <em>3</em></code>, so the total is <code><em>3</em></code>&#39;s &lt;ok&gt;.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><ul><li>A <a href="/x">link</a> and <em>emphasis</em>.</li></ul>
+</article>
+<form method="post"><input type="text" name="answer"/></form>
+</main></body></html>"#;
+
+    #[test]
+    fn downloads_input() {
+        let (url, requests) = serve("200 OK", "1 2\n3 4\n");
+        let client = Client::new(&url, "abc");
+
+        assert_eq!(
+            client
+                .get_input(PuzzleId::new(year!(2024), day!(1)))
+                .unwrap(),
+            "1 2\n3 4\n"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.head.starts_with("GET /2024/day/1/input "));
+        assert!(request.head.contains("session=abc"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (url, requests) = serve("200 OK", PUZZLE_HTML);
+        let client = Client::new(&url, "abc");
+
+        let puzzle = client
+            .get_puzzle(PuzzleId::new(year!(2024), day!(1)))
+            .unwrap();
+        assert!(puzzle.starts_with("## --- Day 1: Historian Hysteria ---\n\n"));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .head
+            .starts_with("GET /2024/day/1 "));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = serve(
+            "200 OK",
+            "<main>\n<article><p>That's not the right answer; your answer is too high.  <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>\n</main>",
+        );
+        let client = Client::new(&url, "abc");
+
+        assert_eq!(
+            client
+                .submit(PuzzleId::new(year!(2024), day!(1)), 2, "42")
+                .unwrap(),
+            "That's not the right answer; your answer is too high. [Return to Day 1]"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.head.starts_with("POST /2024/day/1/answer "));
+        assert_eq!(request.body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn parses_submission_verdicts() {
+        assert_eq!(
+            SubmissionVerdict::parse("That's the right answer! You are one gold star closer to finding the Chief Historian."),
+            SubmissionVerdict::Correct
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("That's not the right answer; your answer is too low."),
+            SubmissionVerdict::TooLow
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("You don't seem to be solving the right level. Did you already complete it? [Return to Day 1]"),
+            SubmissionVerdict::AlreadySolved
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 46s left to wait."),
+            SubmissionVerdict::RateLimited(Some(Duration::from_secs(46)))
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 5s left to wait."),
+            SubmissionVerdict::RateLimited(Some(Duration::from_secs(245)))
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("You gave an answer too recently."),
+            SubmissionVerdict::RateLimited(None)
+        );
+        assert_eq!(
+            SubmissionVerdict::parse("Please log in."),
+            SubmissionVerdict::Unknown
+        );
+    }
+
+    #[test]
+    fn handles_bad_status() {
+        let (url, _requests) = serve("404 Not Found", "Not found");
+        let client = Client::new(&url, "abc");
+
+        assert!(matches!(
+            client.get_input(PuzzleId::new(year!(2024), day!(24))),
+            Err(AocClientError::BadStatus(404))
+        ));
+    }
+
+    #[test]
+    fn handles_login_pages() {
+        let login = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+        for status in ["400 Bad Request", "200 OK"] {
+            let (url, _requests) = serve(status, login);
+            let client = Client::new(&url, "abc");
+
+            assert!(matches!(
+                client.get_input(PuzzleId::new(year!(2024), day!(1))),
+                Err(AocClientError::NotLoggedIn)
+            ));
+        }
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        assert_eq!(
+            puzzle_to_markdown(PUZZLE_HTML),
+            "## --- Day 1: Historian Hysteria ---\n\nFor example:\n\n```\n3   4\n4   3\n```\n\nIn the example above, this is `2 + 1 = *3*`, so the total is `*3*`'s <ok>.\n\nYour puzzle answer was `1234`.\n\n## --- Part Two ---\n\n- A link and *emphasis*.\n\n"
+        );
+    }
+
+    #[test]
+    fn converts_puzzles_for_example_extraction() {
+        let puzzle = Puzzle::parse(&puzzle_to_markdown(PUZZLE_HTML));
+        assert_eq!(puzzle.blocks[0].content, "3   4\n4   3\n");
+        assert_eq!(puzzle.answers, [Some("3".into()), None]);
+    }
+
+    #[test]
+    fn collapses_whitespace() {
+        assert_eq!(html_to_markdown("<p>a\n  <em>b</em> c</p>"), "a *b* c\n\n");
+    }
+
+    #[test]
+    fn skips_emphasis_in_code_blocks() {
+        assert_eq!(
+            html_to_markdown("<pre><code>a<em>b</em></code></pre>"),
+            "```\nab\n```\n\n"
+        );
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;&#62;&#x41;&amp;&unknown; & x"),
+            "<>A&&unknown; & x"
+        );
+    }
+}
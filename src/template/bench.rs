@@ -0,0 +1,470 @@
+/// Benchmarking of solution parts and summary statistics over the collected samples.
+use std::{
+    cmp,
+    collections::HashMap,
+    fmt::Display,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use tinyjson::JsonValue;
+
+/// Samples further than this many interquartile ranges outside of the quartiles are discarded.
+const OUTLIER_IQR_FACTOR: f64 = 3.0;
+
+/// Controls how long and how often a part is benched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time spent collecting samples.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Hard limit on the wall-clock time spent benching a part, overrides `min_samples`.
+    pub max_time: Option<Duration>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            max_time: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the config from command-line arguments, falling back to defaults for missing values.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let defaults = BenchConfig::default();
+
+        let config = BenchConfig {
+            budget: args
+                .opt_value_from_fn("--budget", parse_duration)?
+                .unwrap_or(defaults.budget),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(defaults.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(defaults.max_samples),
+            max_time: args.opt_value_from_fn("--max-time", parse_duration)?,
+        };
+
+        if config.min_samples == 0 || config.min_samples > config.max_samples {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "expected `--min-samples` to be between 1 and `--max-samples`".into(),
+            });
+        }
+
+        Ok(config)
+    }
+
+    /// Read the config from the arguments of the current process, ignoring unrelated arguments.
+    pub fn from_env() -> Result<Self, pico_args::Error> {
+        BenchConfig::from_args(&mut pico_args::Arguments::from_env())
+    }
+
+    /// Convert the config to command-line arguments, e.g. to forward it to a child process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--budget".into(),
+            format!("{}ns", self.budget.as_nanos()),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ];
+
+        if let Some(max_time) = self.max_time {
+            args.push("--max-time".into());
+            args.push(format!("{}ns", max_time.as_nanos()));
+        }
+
+        args
+    }
+
+    fn iterations(&self, base_time: &Duration) -> u128 {
+        (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+/// Parse a duration like `1s`, `250ms`, `1.5µs` or `100ns`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split_at = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split_at);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}`, expecting e.g. `500ms` or `2s`"))?;
+
+    let factor = match unit.trim() {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        "m" => 60e9,
+        _ => {
+            return Err(format!(
+                "invalid duration unit in `{s}`, expecting one of: ns, µs, ms, s, m"
+            ))
+        }
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(Duration::from_nanos((value * factor).round() as u64))
+}
+
+/// Summary statistics of a benchmark, in nanoseconds.
+/// Outliers are excluded from all values except `outliers`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub stddev: f64,
+    pub samples: u64,
+    pub outliers: u64,
+}
+
+/// A statistic of [`BenchStats`] that can be used to represent a benchmark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Statistic {
+    Min,
+    Median,
+    #[default]
+    Mean,
+    P95,
+}
+
+impl BenchStats {
+    /// Compute statistics from a set of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut sorted: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - OUTLIER_IQR_FACTOR * iqr, q3 + OUTLIER_IQR_FACTOR * iqr);
+
+        let total = sorted.len();
+        sorted.retain(|x| *x >= lower && *x <= upper);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / len;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        Some(BenchStats {
+            min: sorted[0],
+            median: percentile(&sorted, 0.5),
+            mean,
+            p95: percentile(&sorted, 0.95),
+            stddev: variance.sqrt(),
+            samples: sorted.len() as u64,
+            outliers: (total - sorted.len()) as u64,
+        })
+    }
+
+    /// Get the value of a statistic in nanoseconds.
+    pub fn get(&self, statistic: Statistic) -> f64 {
+        match statistic {
+            Statistic::Min => self.min,
+            Statistic::Median => self.median,
+            Statistic::Mean => self.mean,
+            Statistic::P95 => self.p95,
+        }
+    }
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+/// Format a number of nanoseconds like a [`Duration`].
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/// Bench a function. A number of warmup iterations are run before samples are collected.
+/// Benching stops early once `config.max_time` is exceeded.
+pub fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> BenchStats {
+    let bench_iterations = config.iterations(base_time);
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+
+    let started = Instant::now();
+    let is_over_time = || config.max_time.is_some_and(|max| started.elapsed() >= max);
+
+    for _ in 0..warmup_iterations {
+        if is_over_time() {
+            break;
+        }
+        black_box(func(black_box(input.clone())));
+    }
+
+    let mut timers: Vec<Duration> = vec![];
+
+    for _ in 0..bench_iterations {
+        if is_over_time() {
+            break;
+        }
+        // need a clone here to make the borrow checker happy.
+        let cloned = input.clone();
+        let timer = Instant::now();
+        black_box(func(black_box(cloned)));
+        timers.push(timer.elapsed());
+    }
+
+    // fall back to the initial run if the time limit did not allow for any samples.
+    if timers.is_empty() {
+        timers.push(*base_time);
+    }
+
+    // NOTE: `timers` is never empty, so there are always stats.
+    BenchStats::from_samples(&timers).unwrap()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Statistic::Min => "min",
+            Statistic::Median => "median",
+            Statistic::Mean => "mean",
+            Statistic::P95 => "p95",
+        })
+    }
+}
+
+impl FromStr for Statistic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min" => Ok(Statistic::Min),
+            "median" => Ok(Statistic::Median),
+            "mean" => Ok(Statistic::Mean),
+            "p95" => Ok(Statistic::P95),
+            _ => Err(format!(
+                "unknown statistic `{s}`, expecting one of: min, median, mean, p95"
+            )),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            min: get_number("min")?,
+            median: get_number("median")?,
+            mean: get_number("mean")?,
+            p95: get_number("p95")?,
+            stddev: get_number("stddev")?,
+            samples: get_number("samples")? as u64,
+            outliers: get_number("outliers")? as u64,
+        })
+    }
+}
+
+/// Read an optional stats value from a JSON object.
+/// Both a missing key and `null` are treated as absent, which keeps files written by older versions readable.
+pub fn stats_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<BenchStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => BenchStats::try_from(v).map(Some),
+    }
+}
+
+/// Convert an optional stats value to JSON.
+pub fn stats_to_json(stats: Option<&BenchStats>) -> JsonValue {
+    match stats {
+        Some(x) => JsonValue::from(x),
+        None => JsonValue::Null,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_nanos, parse_duration, BenchConfig, BenchStats, Statistic};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.p95, 4.8);
+        assert_eq!(stats.stddev, 2_f64.sqrt());
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 1000])).unwrap();
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.mean, 64.0 / 6.0);
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[7])).unwrap();
+        assert_eq!(stats.min, 7.0);
+        assert_eq!(stats.p95, 7.0);
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn parses_statistics() {
+        assert_eq!("median".parse::<Statistic>(), Ok(Statistic::Median));
+        assert_eq!("p95".parse::<Statistic>(), Ok(Statistic::P95));
+        assert!("p99".parse::<Statistic>().is_err());
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74_130.0), "74.1µs");
+        assert_eq!(format_nanos(1_800_000_000.0), "1.8s");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5µs"), Ok(Duration::from_nanos(1500)));
+        assert_eq!(parse_duration("1.5us"), Ok(Duration::from_nanos(1500)));
+        assert_eq!(parse_duration("100ns"), Ok(Duration::from_nanos(100)));
+        assert_eq!(parse_duration("1m"), Ok(Duration::from_secs(60)));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("10h").is_err());
+    }
+
+    #[test]
+    fn roundtrips_bench_config_args() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(500),
+            min_samples: 3,
+            max_samples: 50,
+            max_time: Some(Duration::from_secs(5)),
+        };
+        let args = config.to_args().into_iter().map(Into::into).collect();
+        let parsed = BenchConfig::from_args(&mut pico_args::Arguments::from_vec(args)).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn defaults_bench_config() {
+        let parsed = BenchConfig::from_args(&mut pico_args::Arguments::from_vec(vec![])).unwrap();
+        assert_eq!(parsed, BenchConfig::default());
+    }
+
+    #[test]
+    fn rejects_invalid_sample_bounds() {
+        let args = vec![
+            "--min-samples".into(),
+            "20".into(),
+            "--max-samples".into(),
+            "10".into(),
+        ];
+        assert!(BenchConfig::from_args(&mut pico_args::Arguments::from_vec(args)).is_err());
+    }
+
+    #[test]
+    fn computes_iterations() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations(&Duration::from_millis(1800)), 10);
+        assert_eq!(config.iterations(&Duration::from_millis(10)), 100);
+        assert_eq!(config.iterations(&Duration::from_nanos(30)), 10000);
+
+        let config = BenchConfig {
+            budget: Duration::from_secs(10),
+            min_samples: 2,
+            max_samples: 1_000_000,
+            max_time: None,
+        };
+        assert_eq!(config.iterations(&Duration::from_millis(1800)), 5);
+        assert_eq!(config.iterations(&Duration::from_nanos(30)), 1_000_000);
+    }
+}
@@ -0,0 +1,104 @@
+/// Local cache of downloaded inputs and puzzle descriptions, kept outside of the repository.
+/// Files are keyed by their path in the data folder, e.g. `data/2024/inputs/01.txt` is cached as `<cache>/2024/inputs/01.txt`,
+/// so a fresh clone can restore them without a network connection.
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable with the cache folder. Takes precedence over the default cache folder.
+pub static CACHE_DIR_ENV_VAR: &str = "AOC_CACHE_DIR";
+
+/// Name of the cache folder in the cache folder of the user.
+static CACHE_DIR_NAME: &str = "advent_of_code";
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    /// Locate the cache: `AOC_CACHE_DIR` if set, otherwise `advent_of_code` in `$XDG_CACHE_HOME` or `~/.cache`.
+    /// Returns `None` if no home folder is known.
+    pub fn from_env() -> Option<Self> {
+        if let Some(dir) = env::var_os(CACHE_DIR_ENV_VAR).filter(|x| !x.is_empty()) {
+            return Some(Cache::new(dir));
+        }
+
+        let base = env::var_os("XDG_CACHE_HOME")
+            .filter(|x| !x.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .or_else(|| env::var_os("USERPROFILE"))
+                    .map(|home| PathBuf::from(home).join(".cache"))
+            })?;
+
+        Some(Cache::new(base.join(CACHE_DIR_NAME)))
+    }
+
+    /// Get the cached copy of a file in the data folder. `None` for files outside of it.
+    fn cached_path(&self, path: &Path) -> Option<PathBuf> {
+        path.strip_prefix("data").ok().map(|x| self.dir.join(x))
+    }
+
+    /// Read the cached copy of a file in the data folder, if there is a non-empty one.
+    pub fn read(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(self.cached_path(path)?)
+            .ok()
+            .filter(|x| !x.trim().is_empty())
+    }
+
+    /// Store a copy of a file in the data folder.
+    pub fn store(&self, path: &Path, contents: &str) -> Result<(), io::Error> {
+        let Some(cached_path) = self.cached_path(path) else {
+            return Ok(());
+        };
+
+        if let Some(dir) = cached_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(cached_path, contents)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use super::Cache;
+
+    #[test]
+    fn stores_files_by_data_path() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", process::id()));
+        let cache = Cache::new(&dir);
+        let path = Path::new("data/2024/inputs/01.txt");
+
+        assert_eq!(cache.read(path), None);
+
+        cache.store(path, "1 2\n").unwrap();
+        assert_eq!(cache.read(path), Some("1 2\n".into()));
+        assert_eq!(
+            fs::read_to_string(dir.join("2024/inputs/01.txt")).unwrap(),
+            "1 2\n"
+        );
+
+        cache.store(path, "\n").unwrap();
+        assert_eq!(cache.read(path), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn ignores_files_outside_of_data() {
+        let cache = Cache::new(env::temp_dir().join("aoc-cache-unused"));
+        assert_eq!(cache.read(Path::new("src/bin/2024-01.rs")), None);
+        assert!(cache.store(Path::new("src/bin/2024-01.rs"), "").is_ok());
+    }
+}
@@ -0,0 +1,343 @@
+/// Log-scale bar charts of benchmark timings, rendered as SVG for the readme and as text for the terminal.
+use std::{fmt::Write, fs, io};
+
+use crate::template::bench::{format_nanos, Statistic};
+use crate::template::timings::Timings;
+use crate::template::{Day, Year};
+
+static SVG_DIR: &str = "./.assets";
+
+/// Width of the bars of the terminal chart, in characters.
+const ASCII_WIDTH: usize = 40;
+
+/// Block characters for eighths of a character cell.
+const BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+const SVG_WIDTH: usize = 720;
+const SVG_MARGIN_LEFT: usize = 64;
+const SVG_MARGIN_RIGHT: usize = 80;
+const SVG_MARGIN_TOP: usize = 40;
+const SVG_MARGIN_BOTTOM: usize = 32;
+const SVG_ROW_HEIGHT: usize = 24;
+const SVG_BAR_HEIGHT: usize = 16;
+
+/// A logarithmic scale between two powers of ten.
+#[derive(Debug, PartialEq)]
+struct Scale {
+    /// Exponent of the lowest decade.
+    lo: i32,
+    /// Exponent of the highest decade.
+    hi: i32,
+}
+
+impl Scale {
+    /// Fit a scale around `values`. Returns `None` if there is no positive value.
+    fn fit(values: impl Iterator<Item = f64>) -> Option<Self> {
+        let (min, max) = values
+            .filter(|x| *x > 0.0)
+            .fold(None, |acc: Option<(f64, f64)>, x| match acc {
+                Some((min, max)) => Some((min.min(x), max.max(x))),
+                None => Some((x, x)),
+            })?;
+
+        // NOTE: keep a bit of room below the fastest day, so that its bar does not vanish.
+        #[allow(clippy::cast_possible_truncation)]
+        let lo = ((min.log10() - 0.1).floor() as i32).max(0);
+        #[allow(clippy::cast_possible_truncation)]
+        let hi = (max.log10().ceil() as i32).max(lo + 1);
+
+        Some(Scale { lo, hi })
+    }
+
+    /// Position of `nanos` on the scale, from `0.0` to `1.0`.
+    fn position(&self, nanos: f64) -> f64 {
+        let range = f64::from(self.hi - self.lo);
+        ((nanos.max(1.0).log10() - f64::from(self.lo)) / range).clamp(0.0, 1.0)
+    }
+
+    fn decades(&self) -> impl Iterator<Item = i32> {
+        self.lo..=self.hi
+    }
+}
+
+/// Format a power of ten nanoseconds, e.g. `100µs` for `5`.
+fn format_decade(exponent: i32) -> String {
+    let (unit_exponent, unit) = [(9, "s"), (6, "ms"), (3, "µs"), (0, "ns")]
+        .into_iter()
+        .find(|(unit_exponent, _)| exponent >= *unit_exponent)
+        .unwrap_or((0, "ns"));
+
+    format!(
+        "{}{unit}",
+        10_u64.pow((exponent - unit_exponent).unsigned_abs())
+    )
+}
+
+/// Get the total duration of each benchmarked day, represented by a statistic.
+fn day_totals(timings: &Timings, statistic: Statistic) -> Vec<(Day, f64)> {
+    timings
+        .data
+        .iter()
+        .map(|t| (t.day, t.total_nanos_by(statistic)))
+        .filter(|(_, nanos)| *nanos > 0.0)
+        .collect()
+}
+
+/// Render a bar of `width` characters, filled to `position`.
+fn ascii_bar(position: f64, width: usize) -> String {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let eighths = (position * (width * 8) as f64).round() as usize;
+
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(BLOCKS[eighths % 8 - 1]);
+    }
+
+    format!("{bar:<width$}")
+}
+
+/// Render the total duration of each day as a log-scale bar chart for the terminal.
+/// Returns `None` if there are no timings.
+pub fn ascii_chart(timings: &Timings, statistic: Statistic) -> Option<String> {
+    let rows = day_totals(timings, statistic);
+    let scale = Scale::fit(rows.iter().map(|x| x.1))?;
+
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|(day, nanos)| {
+            format!(
+                "Day {:>2} │{} {}",
+                day.into_inner(),
+                ascii_bar(scale.position(*nanos), ASCII_WIDTH),
+                format_nanos(*nanos)
+            )
+        })
+        .collect();
+
+    let mut axis: Vec<char> = "─".repeat(ASCII_WIDTH + 1).chars().collect();
+    let mut labels: Vec<char> = vec![' '; ASCII_WIDTH + 8];
+    let mut next_free = 0;
+
+    for decade in scale.decades() {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let column = (scale.position(10_f64.powi(decade)) * ASCII_WIDTH as f64).round() as usize;
+        axis[column] = '┴';
+
+        // NOTE: skip labels that would overlap the previous one.
+        let label: Vec<char> = format_decade(decade).chars().collect();
+        if column >= next_free && column + label.len() <= labels.len() {
+            labels[column..column + label.len()].copy_from_slice(&label);
+            next_free = column + label.len() + 1;
+        }
+    }
+
+    // NOTE: column 0 of the axis lines up with the left border of the bars.
+    axis[0] = '└';
+    lines.push(format!("       {}", axis.iter().collect::<String>()));
+    lines.push(format!(
+        "       {}",
+        labels.iter().collect::<String>().trim_end()
+    ));
+
+    Some(lines.join("\n"))
+}
+
+/// Render the total duration of each day as a log-scale bar chart in SVG format.
+/// Returns `None` if there are no timings.
+pub fn svg_chart(timings: &Timings, statistic: Statistic) -> Option<String> {
+    let rows = day_totals(timings, statistic);
+    let scale = Scale::fit(rows.iter().map(|x| x.1))?;
+
+    let plot_width = SVG_WIDTH - SVG_MARGIN_LEFT - SVG_MARGIN_RIGHT;
+    let plot_height = rows.len() * SVG_ROW_HEIGHT;
+    let height = SVG_MARGIN_TOP + plot_height + SVG_MARGIN_BOTTOM;
+
+    #[allow(clippy::cast_precision_loss)]
+    let x = |nanos: f64| SVG_MARGIN_LEFT as f64 + scale.position(nanos) * plot_width as f64;
+
+    let title = if statistic == Statistic::default() {
+        "Benchmarks (log scale)".to_string()
+    } else {
+        format!("Benchmarks ({statistic}, log scale)")
+    };
+
+    let mut svg = String::new();
+
+    // NOTE: writing to a `String` can't fail.
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{height}" viewBox="0 0 {SVG_WIDTH} {height}" font-family="ui-monospace, SFMono-Regular, Menlo, monospace" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        "<style>text {{ fill: #57606a; }} .bar {{ fill: #f0883e; }} .grid {{ stroke: #d0d7de; }}</style>"
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{SVG_MARGIN_LEFT}" y="20" font-size="14">{title}</text>"#
+    );
+
+    for decade in scale.decades() {
+        let x = x(10_f64.powi(decade));
+        let _ = writeln!(
+            svg,
+            r#"<line class="grid" x1="{x:.1}" y1="{SVG_MARGIN_TOP}" x2="{x:.1}" y2="{}" />"#,
+            SVG_MARGIN_TOP + plot_height
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            SVG_MARGIN_TOP + plot_height + 20,
+            format_decade(decade)
+        );
+    }
+
+    for (i, (day, nanos)) in rows.iter().enumerate() {
+        let y = SVG_MARGIN_TOP + i * SVG_ROW_HEIGHT + (SVG_ROW_HEIGHT - SVG_BAR_HEIGHT) / 2;
+        let text_y = y + SVG_BAR_HEIGHT - 4;
+
+        #[allow(clippy::cast_precision_loss)]
+        let width = (x(*nanos) - SVG_MARGIN_LEFT as f64).max(1.0);
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{text_y}" text-anchor="end">Day {}</text>"#,
+            SVG_MARGIN_LEFT - 8,
+            day.into_inner()
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect class="bar" x="{SVG_MARGIN_LEFT}" y="{y}" width="{width:.1}" height="{SVG_BAR_HEIGHT}" rx="2" />"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{text_y}">{}</text>"#,
+            x(*nanos) + 6.0,
+            format_nanos(*nanos)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    Some(svg)
+}
+
+/// Write the SVG chart of a year to `.assets/benchmarks-<year>.svg`. Does nothing if there are no timings.
+pub fn store_svg(year: Year, timings: &Timings, statistic: Statistic) -> Result<(), io::Error> {
+    let Some(svg) = svg_chart(timings, statistic) else {
+        return Ok(());
+    };
+
+    fs::create_dir_all(SVG_DIR)?;
+    fs::write(format!("{SVG_DIR}/benchmarks-{year}.svg"), svg)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ascii_bar, ascii_chart, format_decade, svg_chart, Scale};
+    use crate::{
+        day,
+        template::{
+            bench::Statistic,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+
+    fn get_mock_timings() -> Timings {
+        let timing = |day, nanos| Timing {
+            part_1: Some(PartTiming::from_nanos(nanos)),
+            ..Timing::new(day)
+        };
+
+        Timings {
+            data: vec![
+                timing(day!(1), 33_700.0),
+                timing(day!(6), 1_800_000_000.0),
+                timing(day!(9), 0.0),
+            ],
+            history: vec![],
+        }
+    }
+
+    #[test]
+    fn fits_scales() {
+        let scale = Scale::fit([33_700.0, 1_800_000_000.0].into_iter()).unwrap();
+        assert_eq!(scale, Scale { lo: 4, hi: 10 });
+        assert_eq!(
+            Scale::fit([1_000.0].into_iter()).unwrap(),
+            Scale { lo: 2, hi: 3 }
+        );
+        assert_eq!(Scale::fit([0.0].into_iter()), None);
+    }
+
+    #[test]
+    fn positions_on_scale() {
+        let scale = Scale { lo: 3, hi: 6 };
+        assert_eq!(scale.position(1_000.0), 0.0);
+        assert_eq!(scale.position(1_000_000.0), 1.0);
+        assert_eq!(scale.position(10_000_000.0), 1.0);
+        assert!((scale.position(31_622.0) - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(3), "1µs");
+        assert_eq!(format_decade(7), "10ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn renders_ascii_bars() {
+        assert_eq!(ascii_bar(0.0, 4), "    ");
+        assert_eq!(ascii_bar(0.5, 4), "██  ");
+        assert_eq!(ascii_bar(0.59375, 4), "██▍ ");
+        assert_eq!(ascii_bar(1.0, 4), "████");
+    }
+
+    #[test]
+    fn renders_ascii_chart() {
+        let chart = ascii_chart(&get_mock_timings(), Statistic::Mean).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].starts_with("Day  1 │"), true);
+        assert_eq!(lines[0].ends_with(" 33.7µs"), true);
+        assert_eq!(
+            lines[1].starts_with("Day  6 │████████████████████████████████"),
+            true
+        );
+        assert_eq!(lines[1].ends_with(" 1.8s"), true);
+        assert_eq!(lines[2].starts_with("       └"), true);
+        assert_eq!(lines[3].trim_start().starts_with("10µs"), true);
+    }
+
+    #[test]
+    fn renders_svg_chart() {
+        let svg = svg_chart(&get_mock_timings(), Statistic::Median).unwrap();
+
+        assert_eq!(svg.starts_with("<svg "), true);
+        assert_eq!(svg.trim_end().ends_with("</svg>"), true);
+        assert_eq!(svg.contains("Benchmarks (median, log scale)"), true);
+        assert_eq!(svg.matches("<rect ").count(), 2);
+        assert_eq!(svg.contains(">Day 6</text>"), true);
+        assert_eq!(svg.contains(">Day 9</text>"), false);
+        assert_eq!(svg.contains(">1.8s</text>"), true);
+    }
+
+    #[test]
+    fn skips_empty_timings() {
+        assert_eq!(ascii_chart(&Timings::default(), Statistic::Mean), None);
+        assert_eq!(svg_chart(&Timings::default(), Statistic::Mean), None);
+    }
+}
@@ -1,5 +1,34 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::timings::Timings;
+use crate::template::{run_multi::run_multi, DaySelection, TimeoutConfig, Year};
+
+pub fn handle(
+    year: Year,
+    selection: &DaySelection,
+    is_release: bool,
+    jobs: usize,
+    timeouts: &TimeoutConfig,
+) {
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read stored timings: {e}");
+        process::exit(1);
+    });
+
+    let days_to_run = selection.resolve(year, &stored_timings);
+
+    let run = run_multi(
+        year,
+        &days_to_run,
+        is_release,
+        None,
+        timeouts,
+        jobs,
+        selection.part,
+        false,
+    );
+
+    if !run.mismatches.is_empty() || !run.build_failures.is_empty() {
+        process::exit(1);
+    }
 }
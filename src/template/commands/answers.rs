@@ -0,0 +1,53 @@
+use std::process;
+
+use crate::template::{all_days, answers::Answers, readme_benchmarks, PuzzleId, Year};
+
+/// Print all confirmed answers of a year.
+pub fn handle_list(year: Year) {
+    let answers = read_answers(year);
+
+    if answers.data.is_empty() {
+        println!("No confirmed answers for {year} yet.");
+        return;
+    }
+
+    for day in all_days(year) {
+        for part in 1..=2 {
+            if let Some(answer) = answers.get(day, part) {
+                println!("Day {day} / Part {part}: {answer}");
+            }
+        }
+    }
+}
+
+/// Store a confirmed answer, e.g. for a star that was earned without `--submit`.
+pub fn handle_set(puzzle: PuzzleId, part: u8, answer: &str) {
+    let PuzzleId { year, day } = puzzle;
+
+    if part != 1 && part != 2 {
+        eprintln!("Expected part to be 1 or 2.");
+        process::exit(1);
+    }
+
+    let mut answers = read_answers(year);
+    answers.set(day, part, answer);
+
+    if let Err(e) = answers.store_file(year) {
+        eprintln!("Failed to store answer: {e}");
+        process::exit(1);
+    }
+
+    println!("Stored answer for day {day} of {year}, part {part}.");
+
+    if readme_benchmarks::update_stars(year).is_ok_and(|x| x) {
+        println!("Updated stars in the readme.");
+    }
+}
+
+/// Read the answer registry of a year, exiting if it can not be parsed.
+fn read_answers(year: Year) -> Answers {
+    Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read answer registry: {e}");
+        process::exit(1);
+    })
+}
@@ -0,0 +1,47 @@
+use std::process;
+
+use crate::template::compare::{compare_timings, format_comparison, TableFormat};
+use crate::template::timings::Timings;
+use crate::template::{Statistic, Year};
+
+/// Compare two sets of timings. `new` defaults to the timings currently stored in `data/<year>/timings.json`.
+pub fn handle(year: Year, old: &str, new: Option<&str>, statistic: Statistic, format: TableFormat) {
+    let stored = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read stored timings: {e}");
+        process::exit(1);
+    });
+
+    let load = |spec: &str| {
+        load_snapshot(&stored, spec).unwrap_or_else(|e| {
+            eprintln!("Failed to load timings `{spec}`: {e}");
+            process::exit(1);
+        })
+    };
+
+    let old_timings = load(old);
+    let new_timings = new.map_or_else(|| stored.clone(), load);
+
+    let deltas = compare_timings(&old_timings, &new_timings, statistic);
+
+    if deltas.is_empty() {
+        eprintln!("The timings have no parts in common.");
+        process::exit(1);
+    }
+
+    println!("{}", format_comparison(&deltas, format));
+}
+
+/// Load a set of timings. `@<reference>` refers to an entry of the stored history (see [`Timings::find_history`]),
+/// anything else is read as a path to a timings file.
+fn load_snapshot(stored: &Timings, spec: &str) -> Result<Timings, String> {
+    match spec.strip_prefix('@') {
+        Some(reference) => stored
+            .find_history(reference)
+            .map(|entry| Timings {
+                data: entry.data.clone(),
+                history: vec![],
+            })
+            .ok_or_else(|| "no matching entry in the benchmark history.".into()),
+        None => Timings::read_from_path(spec),
+    }
+}
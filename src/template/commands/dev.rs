@@ -0,0 +1,82 @@
+use std::process::Command;
+
+use crate::template::run_multi::{child_commands, Printer};
+use crate::template::watch::{
+    answers_from_reports, format_answer_diff, wait_for_change, watched_paths, Answers, Snapshot,
+};
+use crate::template::{PuzzleId, TimeoutConfig, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// Rerun the tests and the solution of a day whenever one of its files changes.
+pub fn handle(puzzle: PuzzleId, release: bool, timeouts: &TimeoutConfig) {
+    let mut snapshot = Snapshot::take(&watched_paths(puzzle));
+    let mut previous: Option<Answers> = None;
+
+    loop {
+        if let Some(answers) = run(puzzle, release, timeouts) {
+            if let Some(previous) = &previous {
+                println!("\n{}", format_answer_diff(previous, &answers));
+            }
+            previous = Some(answers);
+        }
+
+        println!(
+            "\n{ANSI_ITALIC}Watching day {} of {} for changes...{ANSI_RESET}",
+            puzzle.day, puzzle.year
+        );
+
+        let (next, changed) = wait_for_change(puzzle, &snapshot);
+        snapshot = next;
+
+        let changed: Vec<String> = changed.iter().map(|x| x.display().to_string()).collect();
+        println!("\n{ANSI_BOLD}Changed:{ANSI_RESET} {}\n", changed.join(", "));
+    }
+}
+
+/// Run the tests of a day against its examples, then its solution against the real input.
+/// Returns the answers of the solution, unless it failed to run.
+fn run(puzzle: PuzzleId, release: bool, timeouts: &TimeoutConfig) -> Option<Answers> {
+    let mut args = vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        puzzle.to_string(),
+    ];
+
+    if release {
+        args.push("--release".into());
+    }
+
+    println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+    println!("--------");
+
+    match Command::new("cargo").args(&args).status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => println!("{ANSI_RED}Tests failed.{ANSI_RESET}"),
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
+
+    println!("\n{ANSI_BOLD}Input{ANSI_RESET}");
+    println!("-----");
+
+    let output = match child_commands::run_solution(
+        puzzle,
+        None,
+        release,
+        timeouts,
+        None,
+        false,
+        &mut Printer::streaming(),
+    ) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run solution: {e:?}");
+            return None;
+        }
+    };
+
+    if output.reports.is_empty() {
+        return None;
+    }
+
+    Some(answers_from_reports(&output.reports))
+}
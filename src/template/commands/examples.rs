@@ -0,0 +1,271 @@
+use std::{
+    fs,
+    process::{self, Command},
+};
+
+use crate::template::examples::{format_answer, Example, Examples, Expected, PartCheck};
+use crate::template::puzzle::{ExtractedExample, Puzzle};
+use crate::template::report::{PartReport, JSON_FLAG};
+use crate::template::runner::INPUT_FLAG;
+use crate::template::{PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// Run the solution of a day against all of its examples and check the answers.
+pub fn handle(puzzle: PuzzleId, release: bool) {
+    let examples = match Examples::read_from_file(puzzle) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("Failed to read examples of {puzzle}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if !examples.data.iter().any(Example::is_checked) {
+        eprintln!(
+            "No examples with expected answers for {puzzle}. Add them to \"{}\".",
+            Examples::file_path(puzzle).display()
+        );
+        process::exit(1);
+    }
+
+    let mut args: Vec<String> = vec!["--quiet".into(), "--bin".into(), puzzle.to_string()];
+
+    if release {
+        args.push("--release".into());
+    }
+
+    // build once ahead of running, so compiler output is not repeated for every example.
+    match Command::new("cargo").arg("build").args(&args).status() {
+        Ok(status) if status.success() => {}
+        _ => process::exit(1),
+    }
+
+    let mut failed = 0;
+
+    for example in examples.data.iter().filter(|x| x.is_checked()) {
+        println!("{ANSI_BOLD}{}{ANSI_RESET} ({})", example.name, example.file);
+
+        let checks = match run_example(&args, puzzle.year, example) {
+            Ok(checks) => checks,
+            Err(e) => {
+                println!("{ANSI_RED}{e}{ANSI_RESET}");
+                failed += 1;
+                continue;
+            }
+        };
+
+        for check in &checks {
+            println!("{}", format_check(check));
+        }
+
+        if checks.iter().any(|x| !x.is_ok()) {
+            failed += 1;
+        }
+    }
+
+    let total = examples.data.iter().filter(|x| x.is_checked()).count();
+
+    if failed > 0 {
+        println!("\n{ANSI_RED}{failed} of {total} example(s) failed.{ANSI_RESET}");
+        process::exit(1);
+    }
+
+    println!("\n{ANSI_ITALIC}All {total} example(s) passed.{ANSI_RESET}");
+}
+
+/// Run the solution binary with an example as input and check the reported answers.
+fn run_example(args: &[String], year: Year, example: &Example) -> Result<Vec<PartCheck>, String> {
+    let mut cmd_args: Vec<String> = vec!["run".into()];
+    cmd_args.extend_from_slice(args);
+    cmd_args.push("--".into());
+    cmd_args.push(JSON_FLAG.into());
+    cmd_args.push(INPUT_FLAG.into());
+    cmd_args.push(example.path(year).display().to_string());
+
+    if let Some(part) = example.part_filter() {
+        cmd_args.push("--part".into());
+        cmd_args.push(part.to_string());
+    }
+
+    let output = Command::new("cargo")
+        .args(&cmd_args)
+        .output()
+        .map_err(|e| format!("Failed to run solution: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let reports: Vec<PartReport> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(PartReport::from_json_line)
+        .collect();
+
+    Ok(example.check(&reports))
+}
+
+fn format_check(check: &PartCheck) -> String {
+    let part = check.part;
+
+    match &check.actual {
+        _ if check.is_ok() => format!(
+            "Part {part}: ✔ {}",
+            check.expected.as_deref().unwrap_or("no answer")
+        ),
+        Some(actual) => format!(
+            "{ANSI_RED}Part {part}: ✖ {actual}, expected {}{ANSI_RESET}",
+            format_answer(check.expected.as_deref())
+        ),
+        None => format!(
+            "{ANSI_RED}Part {part}: ✖ no answer, expected {}{ANSI_RESET}",
+            format_answer(check.expected.as_deref())
+        ),
+    }
+}
+
+/// Extract example inputs and expected answers from the downloaded puzzle description of a day.
+/// With `list`, only the code blocks of the description are printed. `block` (1-based) overrides the picked example.
+pub fn handle_extract(id: PuzzleId, block: Option<usize>, list: bool, overwrite: bool) {
+    let PuzzleId { year, day } = id;
+    let puzzle_path = id.puzzle_path();
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{}\". Download the puzzle with `cargo download --year {year} {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let puzzle = Puzzle::parse(&markdown);
+
+    if puzzle.blocks.is_empty() {
+        eprintln!("No code blocks found in \"{}\".", puzzle_path.display());
+        process::exit(1);
+    }
+
+    let block = block.map(|x| x.wrapping_sub(1));
+    if block.is_some_and(|x| x >= puzzle.blocks.len()) {
+        eprintln!("Expected a block between 1 and {}.", puzzle.blocks.len());
+        process::exit(1);
+    }
+
+    let extracted = puzzle.extract_examples(block);
+
+    if list {
+        print_blocks(&puzzle, &extracted);
+        return;
+    }
+
+    let mut examples = Examples::read_from_file(id).unwrap_or_else(|e| {
+        eprintln!("Failed to read examples of {id}: {e}");
+        process::exit(1);
+    });
+
+    for (index, extracted) in extracted.iter().enumerate() {
+        let (name, file) = if index == 0 {
+            ("example".to_string(), format!("{day}.txt"))
+        } else {
+            (
+                format!("example-{}", index + 1),
+                format!("{day}-{}.txt", index + 1),
+            )
+        };
+
+        let previous = examples.data.iter().find(|x| x.name == name);
+
+        // NOTE: keep expected answers that could not be found in the description, e.g. set by hand.
+        let expected = [0, 1].map(|part| match &extracted.expected[part] {
+            Some(x) => Expected::Answer(x.clone()),
+            None => previous.map_or(Expected::Unchecked, |x| x.expected[part].clone()),
+        });
+
+        let example = Example {
+            name,
+            file,
+            expected,
+        };
+
+        let content = &puzzle.blocks[extracted.block].content;
+        let path = example.path(year);
+
+        if !overwrite
+            && fs::read_to_string(&path).is_ok_and(|x| !x.trim().is_empty() && x != *content)
+        {
+            eprintln!(
+                "Example file \"{}\" already exists. Pass `--overwrite` to replace it.",
+                path.display()
+            );
+            process::exit(1);
+        }
+
+        if let Err(e) = fs::create_dir_all(year.data_dir().join("examples"))
+            .and_then(|()| fs::write(&path, content))
+        {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+
+        println!(
+            "Wrote example `{}` to \"{}\" ({}).",
+            example.name,
+            path.display(),
+            format_expected(&example)
+        );
+
+        examples.set(example);
+    }
+
+    match examples.store_file(id) {
+        Ok(()) => println!(
+            "Stored examples in \"{}\".",
+            Examples::file_path(id).display()
+        ),
+        Err(e) => {
+            eprintln!("Failed to store examples: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn print_blocks(puzzle: &Puzzle, extracted: &[ExtractedExample]) {
+    for (index, block) in puzzle.blocks.iter().enumerate() {
+        let marker = if extracted.iter().any(|x| x.block == index) {
+            "*"
+        } else {
+            " "
+        };
+
+        println!(
+            "{marker}{ANSI_BOLD}[{}]{ANSI_RESET} part {}, {} line(s): {ANSI_ITALIC}{}{ANSI_RESET}",
+            index + 1,
+            block.part,
+            block.line_count(),
+            block.content.lines().next().unwrap_or_default()
+        );
+    }
+
+    let answers: Vec<String> = puzzle
+        .answers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, answer)| Some(format!("part {}: `{}`", index + 1, answer.as_ref()?)))
+        .collect();
+
+    if !answers.is_empty() {
+        println!("\nAnswers: {}", answers.join(", "));
+    }
+}
+
+fn format_expected(example: &Example) -> String {
+    [1, 2]
+        .map(|part| {
+            format!(
+                "part {part}: {}",
+                example
+                    .expected(part)
+                    .answer()
+                    .map_or("?".into(), format_answer)
+            )
+        })
+        .join(", ")
+}
@@ -1,5 +1,9 @@
 pub mod all;
+pub mod answers;
+pub mod compare;
+pub mod dev;
 pub mod download;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
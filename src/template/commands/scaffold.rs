@@ -1,15 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::examples::{Example, Examples, Expected};
+use crate::template::{PuzzleId, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +21,11 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +33,19 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let PuzzleId { year, day } = puzzle;
+    let input_path = puzzle.input_path();
+    let examples = Examples {
+        data: vec![Example {
+            name: "example".into(),
+            file: format!("{day}.txt"),
+            expected: [Expected::Unchecked, Expected::Unchecked],
+        }],
+    };
+    let example_path = examples.data[0].path(year);
+    let manifest_path = Examples::file_path(puzzle);
+    let module_path = puzzle.bin_path();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +55,23 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let mut module = MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day.into_inner().to_string());
+
+    // NOTE: the puzzle of the last day of the event only has a first part, the solution selects it.
+    if puzzle.part_count() == 1 {
+        if let Some(pos) = module.find("\npub fn part_two") {
+            module.truncate(pos);
+        }
+
+        module = module.replace(
+            &format!("solution!({})", day.into_inner()),
+            &format!("solution!({}, 1)", day.into_inner()),
+        );
+    }
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,19 +79,24 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    // NOTE: inputs are downloaded, so an existing input is kept even with `--overwrite`.
+    if input_path.exists() {
+        println!("Kept existing input file \"{}\"", input_path.display());
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", input_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -74,6 +104,26 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    // NOTE: the manifest holds expected answers, so it is never overwritten.
+    if !manifest_path.exists() {
+        match examples.store_file(puzzle) {
+            Ok(()) => {
+                println!("Created example manifest \"{}\"", manifest_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create example manifest: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    // NOTE: commands default to the year in `AOC_YEAR`, other years need to be passed explicitly.
+    let year_flag = if Year::from_env() == Some(year) {
+        String::new()
+    } else {
+        format!(" --year {year}")
+    };
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve{year_flag} {day}` to run your solution.");
 }
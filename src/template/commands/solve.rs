@@ -1,9 +1,28 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::runner::INPUT_FLAG;
+use crate::template::{BenchConfig, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    wait: bool,
+    input: Option<&str>,
+    bench_config: Option<&BenchConfig>,
+) {
+    if input.is_some() && submit_part.is_some() {
+        eprintln!("Answers for a custom input can't be submitted.");
+        process::exit(1);
+    }
+
+    if submit_part.is_some_and(|part| part > puzzle.part_count()) {
+        eprintln!("Day {} only has a single part.", puzzle.day.into_inner());
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -21,6 +40,20 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if wait {
+            cmd_args.push("--wait".to_string());
+        }
+    }
+
+    if let Some(input) = input {
+        cmd_args.push(INPUT_FLAG.to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    if let Some(bench_config) = bench_config {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench_config.to_args());
     }
 
     let mut cmd = Command::new("cargo")
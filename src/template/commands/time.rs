@@ -1,34 +1,117 @@
-use std::collections::HashSet;
+use std::process;
 
+use crate::template::charts;
+use crate::template::compare::{compare_timings, print_deltas};
+use crate::template::readme_benchmarks::ReadmeConfig;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
+use crate::template::{
+    readme_benchmarks, BenchConfig, DaySelection, RegressionConfig, Statistic, TimeoutConfig, Year,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    selection: &DaySelection,
+    run_all: bool,
+    store: bool,
+    statistic: Statistic,
+    bench_config: &BenchConfig,
+    timeouts: &TimeoutConfig,
+    regressions: &RegressionConfig,
+    memory: bool,
+) {
+    let stored_timings = match Timings::read_from_file(year) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Failed to read stored timings: {e}");
+            process::exit(1);
+        }
+    };
+
+    // NOTE: read the table layout before benching, so a broken config does not waste a run.
+    let readme_config = match ReadmeConfig::read_from_file() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to read readme table config: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut selection = selection.clone();
+
+    // when neither days, filters nor the `--all` flag are set, filter out days that are fully benched.
+    if selection.days.is_none() && selection.slower_than.is_none() && !run_all {
+        selection.only_incomplete = true;
+    }
+
+    let days_to_run = selection.resolve(year, &stored_timings);
+
+    let run = run_multi(
+        year,
+        &days_to_run,
+        true,
+        Some(bench_config),
+        timeouts,
+        1,
+        selection.part,
+        memory,
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if !run.mismatches.is_empty() {
+        if store {
+            eprintln!("\nAnswers do not match the answer registry, benchmarks were not stored.");
+        }
+        process::exit(1);
+    }
+
+    // NOTE: timings are always present for benched runs.
+    let mut timings = run.timings.unwrap();
+
+    if !memory {
+        timings.inherit_memory(&stored_timings);
+    }
+
+    let deltas = compare_timings(&stored_timings, &timings, statistic);
+    print_deltas(&deltas, regressions.threshold);
+
+    let regressed = deltas
+        .iter()
+        .filter(|x| x.is_regression(regressions.threshold))
+        .count();
+
+    if regressed > 0 {
+        eprintln!(
+            "\n{regressed} part(s) regressed by more than {:.0}%.",
+            regressions.threshold * 100.0
+        );
+
+        if regressions.fail {
+            if store {
+                eprintln!("Benchmarks were not stored.");
+            }
+            process::exit(1);
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let mut merged_timings = match selection.part {
+            Some(part) => stored_timings.merge_part(&timings, part),
+            None => stored_timings.merge(&timings),
+        };
+        merged_timings.record_history(&timings);
+        merged_timings.store_file(year).unwrap();
+
+        if let Some(chart) = charts::ascii_chart(&merged_timings, statistic) {
+            println!("\n{chart}");
+        }
+
+        if charts::store_svg(year, &merged_timings, statistic).is_err() {
+            eprintln!("Failed to store benchmark chart.");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, statistic, &readme_config) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -36,5 +119,10 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
+
+        // NOTE: days with timings for both parts count as solved, so stars may have changed as well.
+        if readme_benchmarks::update_stars(year).is_ok_and(|x| x) {
+            println!("Updated stars in the readme.");
+        }
     }
 }
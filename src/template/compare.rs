@@ -0,0 +1,414 @@
+/// Comparison of benchmark timings, e.g. of a new run against stored timings.
+use crate::template::bench::{format_nanos, BenchStats};
+use crate::template::report::{format_step, PARSE_PART};
+use crate::template::timings::Timings;
+use crate::template::{Day, Statistic, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// A change is significant if it exceeds this many standard errors of the difference (~95% confidence).
+const SIGNIFICANCE_Z: f64 = 1.96;
+
+/// Controls when a slower part is reported as a regression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionConfig {
+    /// Relative slowdown above which a part counts as regressed, e.g. `0.1` for 10%.
+    pub threshold: f64,
+    /// Exit with an error when a part regressed.
+    pub fail: bool,
+}
+
+impl Default for RegressionConfig {
+    fn default() -> Self {
+        RegressionConfig {
+            threshold: 0.1,
+            fail: false,
+        }
+    }
+}
+
+impl RegressionConfig {
+    /// Read `--threshold` and `--fail-on-regression` from command-line arguments.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let default = RegressionConfig::default();
+
+        Ok(RegressionConfig {
+            threshold: args
+                .opt_value_from_fn("--threshold", parse_percentage)?
+                .unwrap_or(default.threshold),
+            fail: args.contains("--fail-on-regression"),
+        })
+    }
+}
+
+/// Parse a percentage like `10%` or `10` into a fraction.
+pub fn parse_percentage(s: &str) -> Result<f64, String> {
+    s.trim_end_matches('%')
+        .parse::<f64>()
+        .ok()
+        .filter(|x| x.is_finite() && *x >= 0.0)
+        .map(|x| x / 100.0)
+        .ok_or_else(|| format!("invalid percentage `{s}`, expected a value like `10%`"))
+}
+
+/// The change of a part's duration between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+    pub old_stats: Option<BenchStats>,
+    pub new_stats: Option<BenchStats>,
+}
+
+impl PartDelta {
+    /// The relative change, e.g. `0.5` if the part got 50% slower.
+    pub fn relative_change(&self) -> f64 {
+        if self.old_nanos == 0.0 {
+            0.0
+        } else {
+            (self.new_nanos - self.old_nanos) / self.old_nanos
+        }
+    }
+
+    /// Whether the part got slower by more than `threshold`.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.relative_change() > threshold
+    }
+
+    /// The standard error of the difference, based on the recorded variance of both timings.
+    /// `None` if either timing was recorded without stats.
+    pub fn standard_error(&self) -> Option<f64> {
+        let variance = |stats: &BenchStats| {
+            #[allow(clippy::cast_precision_loss)]
+            let samples = stats.samples.max(1) as f64;
+            stats.stddev.powi(2) / samples
+        };
+
+        Some((variance(self.old_stats.as_ref()?) + variance(self.new_stats.as_ref()?)).sqrt())
+    }
+
+    /// Whether the change is larger than the noise of the recorded samples.
+    pub fn is_significant(&self) -> Option<bool> {
+        self.standard_error()
+            .map(|error| (self.new_nanos - self.old_nanos).abs() > SIGNIFICANCE_Z * error)
+    }
+}
+
+/// Output format of a comparison table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    Terminal,
+    Markdown,
+}
+
+/// Compare the parts present in both `old` and `new`, using a statistic.
+pub fn compare_timings(old: &Timings, new: &Timings, statistic: Statistic) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for new_timing in &new.data {
+        let Some(old_timing) = old.data.iter().find(|t| t.day == new_timing.day) else {
+            continue;
+        };
+
+        for part in [PARSE_PART, 1, 2] {
+            if let (Some(old_nanos), Some(new_nanos)) = (
+                old_timing.part_nanos_by(part, statistic),
+                new_timing.part_nanos_by(part, statistic),
+            ) {
+                deltas.push(PartDelta {
+                    day: new_timing.day,
+                    part,
+                    old_nanos,
+                    new_nanos,
+                    old_stats: old_timing.part_stats(part).cloned(),
+                    new_stats: new_timing.part_stats(part).cloned(),
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Print the change of each part since the stored timings. Regressions are highlighted.
+pub fn print_deltas(deltas: &[PartDelta], threshold: f64) {
+    if deltas.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Changes since last stored run:{ANSI_RESET}");
+
+    for delta in deltas {
+        let line = format!(
+            "Day {} · {}: {} → {} ({:+.1}%)",
+            delta.day,
+            format_step(delta.part),
+            format_nanos(delta.old_nanos),
+            format_nanos(delta.new_nanos),
+            delta.relative_change() * 100.0
+        );
+
+        if delta.is_regression(threshold) {
+            println!("{ANSI_RED}{line} regression{ANSI_RESET}");
+        } else {
+            println!("{ANSI_ITALIC}{line}{ANSI_RESET}");
+        }
+    }
+}
+
+/// Format a per-part comparison table with a total row.
+pub fn format_comparison(deltas: &[PartDelta], format: TableFormat) -> String {
+    let header = [
+        "Day",
+        "Part",
+        "Old",
+        "New",
+        "Change",
+        "Relative",
+        "Significant",
+    ]
+    .map(String::from);
+
+    let mut rows: Vec<([String; 7], bool)> = deltas
+        .iter()
+        .map(|delta| {
+            let significant = delta.is_significant();
+            let row = format_row(
+                delta.day.to_string(),
+                format_step(delta.part),
+                delta.old_nanos,
+                delta.new_nanos,
+                significant,
+            );
+            (
+                row,
+                significant == Some(true) && delta.new_nanos > delta.old_nanos,
+            )
+        })
+        .collect();
+
+    let old_total: f64 = deltas.iter().map(|x| x.old_nanos).sum();
+    let new_total: f64 = deltas.iter().map(|x| x.new_nanos).sum();
+
+    // NOTE: parts are benched independently, so their variances add up.
+    let total_significant = deltas
+        .iter()
+        .map(|x| x.standard_error().map(|error| error.powi(2)))
+        .sum::<Option<f64>>()
+        .map(|variance| (new_total - old_total).abs() > SIGNIFICANCE_Z * variance.sqrt());
+
+    rows.push((
+        format_row(
+            "Total".into(),
+            String::new(),
+            old_total,
+            new_total,
+            total_significant,
+        ),
+        false,
+    ));
+
+    match format {
+        TableFormat::Markdown => {
+            let mut lines = vec![
+                format!("| {} |", header.join(" | ")),
+                format!("|{}", " :---: |".repeat(header.len())),
+            ];
+
+            for (row, _) in rows {
+                lines.push(format!("| {} |", row.join(" | ")));
+            }
+
+            lines.join("\n")
+        }
+        TableFormat::Terminal => {
+            let widths: Vec<usize> = (0..header.len())
+                .map(|i| {
+                    rows.iter()
+                        .map(|(row, _)| &row[i])
+                        .chain([&header[i]])
+                        .map(|x| x.chars().count())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+
+            let pad = |row: &[String; 7]| {
+                row.iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            };
+
+            let mut lines = vec![format!("{ANSI_BOLD}{}{ANSI_RESET}", pad(&header))];
+            let last = rows.len() - 1;
+
+            for (i, (row, is_regression)) in rows.iter().enumerate() {
+                lines.push(if i == last {
+                    format!("{ANSI_BOLD}{}{ANSI_RESET}", pad(row))
+                } else if *is_regression {
+                    format!("{ANSI_RED}{}{ANSI_RESET}", pad(row))
+                } else {
+                    pad(row)
+                });
+            }
+
+            lines.join("\n")
+        }
+    }
+}
+
+fn format_row(
+    day: String,
+    part: String,
+    old_nanos: f64,
+    new_nanos: f64,
+    significant: Option<bool>,
+) -> [String; 7] {
+    let change = new_nanos - old_nanos;
+    let sign = if change < 0.0 { "-" } else { "+" };
+
+    let relative = if old_nanos == 0.0 {
+        "-".into()
+    } else {
+        format!("{:+.1}%", change / old_nanos * 100.0)
+    };
+
+    let significant = match significant {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "-",
+    };
+
+    [
+        day,
+        part,
+        format_nanos(old_nanos),
+        format_nanos(new_nanos),
+        format!("{sign}{}", format_nanos(change.abs())),
+        relative,
+        significant.into(),
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare_timings, format_comparison, parse_percentage, PartDelta, TableFormat};
+    use crate::{
+        day,
+        template::{
+            bench::BenchStats,
+            timings::{PartTiming, Timing, Timings},
+            Day, Statistic,
+        },
+    };
+
+    fn get_mock_stats(mean: f64, stddev: f64) -> BenchStats {
+        BenchStats {
+            min: mean,
+            median: mean,
+            mean,
+            p95: mean,
+            stddev,
+            samples: 100,
+            outliers: 0,
+        }
+    }
+
+    fn get_mock_timings(day: Day, part_1: f64, part_2: Option<f64>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                part_1: Some(PartTiming::from_nanos(part_1)),
+                part_2: part_2.map(PartTiming::from_nanos),
+                ..Timing::new(day)
+            }],
+            history: vec![],
+        }
+    }
+
+    #[test]
+    fn parses_percentages() {
+        assert_eq!(parse_percentage("10%"), Ok(0.1));
+        assert_eq!(parse_percentage("25"), Ok(0.25));
+        assert!(parse_percentage("-5%").is_err());
+        assert!(parse_percentage("ten").is_err());
+    }
+
+    #[test]
+    fn compares_common_parts() {
+        let old = get_mock_timings(day!(1), 10_000_000_f64, None);
+        let new = get_mock_timings(day!(1), 15_000_000_f64, Some(1_000_000_f64));
+
+        let deltas = compare_timings(&old, &new, Statistic::Mean);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].part, 1);
+        assert_eq!(deltas[0].relative_change(), 0.5);
+    }
+
+    #[test]
+    fn skips_new_days() {
+        let old = get_mock_timings(day!(1), 10_000_000_f64, None);
+        let new = get_mock_timings(day!(2), 15_000_000_f64, None);
+        assert!(compare_timings(&old, &new, Statistic::Mean).is_empty());
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let delta = |new_nanos: f64| PartDelta {
+            day: day!(1),
+            part: 1,
+            old_nanos: 100.0,
+            new_nanos,
+            old_stats: None,
+            new_stats: None,
+        };
+
+        assert!(delta(111.0).is_regression(0.1));
+        assert!(!delta(109.0).is_regression(0.1));
+        assert!(!delta(50.0).is_regression(0.1));
+    }
+
+    #[test]
+    fn detects_significant_changes() {
+        let delta = |stddev: f64| PartDelta {
+            day: day!(1),
+            part: 1,
+            old_nanos: 100.0,
+            new_nanos: 110.0,
+            old_stats: Some(get_mock_stats(100.0, stddev)),
+            new_stats: Some(get_mock_stats(110.0, stddev)),
+        };
+
+        assert_eq!(delta(10.0).is_significant(), Some(true));
+        assert_eq!(delta(50.0).is_significant(), Some(false));
+
+        let mut unknown = delta(10.0);
+        unknown.old_stats = None;
+        assert_eq!(unknown.is_significant(), None);
+    }
+
+    #[test]
+    fn formats_markdown_comparisons() {
+        let old = get_mock_timings(day!(1), 10_000_000_f64, Some(2_000_000_f64));
+        let new = get_mock_timings(day!(1), 15_000_000_f64, Some(1_000_000_f64));
+        let deltas = compare_timings(&old, &new, Statistic::Mean);
+
+        assert_eq!(
+            format_comparison(&deltas, TableFormat::Markdown),
+            [
+                "| Day | Part | Old | New | Change | Relative | Significant |",
+                "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+                "| 01 | Part 1 | 10.0ms | 15.0ms | +5.0ms | +50.0% | - |",
+                "| 01 | Part 2 | 2.0ms | 1.0ms | -1.0ms | -50.0% | - |",
+                "| Total |  | 12.0ms | 16.0ms | +4.0ms | +33.3% | - |",
+            ]
+            .join("\n")
+        );
+    }
+}
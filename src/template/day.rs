@@ -1,13 +1,18 @@
 use std::error::Error;
 use std::fmt::Display;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// The last day of advent. Its puzzle only has a first part, the second star is awarded for all other stars.
+/// Events with fewer days end earlier, see [`EventCalendar`].
+const LAST_DAY: u8 = 25;
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -43,20 +48,171 @@ impl Day {
     }
 }
 
+#[cfg(feature = "today")]
+fn server_now() -> Option<DateTime<FixedOffset>> {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+    Some(Utc::now().with_timezone(&offset))
+}
+
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day of the event, see [`PuzzleId::today`].
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        PuzzleId::today().map(|puzzle| puzzle.day)
+    }
+}
+
+/// Prefix of the environment variables with the first date of the event of a year as `MM-DD`, e.g. `AOC_EVENT_START_2025`.
+/// Defaults to `12-01`.
+pub static EVENT_START_ENV_VAR: &str = "AOC_EVENT_START_";
+
+/// Prefix of the environment variables with the number of days of the event of a year, e.g. `AOC_EVENT_DAYS_2025`.
+/// Defaults to `25`.
+pub static EVENT_DAYS_ENV_VAR: &str = "AOC_EVENT_DAYS_";
+
+/// The dates of an event: the day of its first puzzle and how many puzzles it has.
+/// Advent of Code runs from the 1st to the 25th of december, other events can start later or have fewer days.
+/// The puzzle of the last day only has a first part. Each year has its own calendar, see [`EventCalendar::for_year`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCalendar {
+    pub start_month: u32,
+    pub start_day: u32,
+    /// Number of days, from 1 to 25.
+    pub days: u8,
+}
+
+impl Default for EventCalendar {
+    fn default() -> Self {
+        EventCalendar {
+            start_month: 12,
+            start_day: 1,
+            days: LAST_DAY,
+        }
+    }
+}
+
+impl EventCalendar {
+    /// Read the calendar of a year from the `AOC_EVENT_START_<year>` and `AOC_EVENT_DAYS_<year>` environment variables.
+    /// Variables that are not set keep the dates of Advent of Code.
+    pub fn from_env(year: Year) -> Result<Self, String> {
+        EventCalendar::from_lookup(year, |name| std::env::var(name).ok())
+    }
+
+    /// Returns the calendar of a year configured in the environment.
+    /// An invalid configuration falls back to the dates of Advent of Code, it is reported when the CLI starts.
+    pub fn for_year(year: Year) -> Self {
+        EventCalendar::from_env(year).unwrap_or_default()
+    }
+
+    /// Checks the calendar of every year configured in the environment.
+    pub fn check_env() -> Result<(), String> {
+        for (name, _) in std::env::vars() {
+            let Some(year) = [EVENT_START_ENV_VAR, EVENT_DAYS_ENV_VAR]
+                .iter()
+                .find_map(|prefix| name.strip_prefix(prefix))
+            else {
+                continue;
+            };
+
+            let year = year
+                .parse()
+                .map_err(|_| format!("expected `{name}` to end with a year, e.g. `2025`."))?;
+            EventCalendar::from_env(year)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read the calendar of a year from the variables returned by `lookup`.
+    fn from_lookup(year: Year, lookup: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        EventCalendar::from_vars(
+            year,
+            lookup(&format!("{EVENT_START_ENV_VAR}{year}")).as_deref(),
+            lookup(&format!("{EVENT_DAYS_ENV_VAR}{year}")).as_deref(),
+        )
+    }
+
+    /// Parse the calendar of a year from the values of its environment variables.
+    fn from_vars(year: Year, start: Option<&str>, days: Option<&str>) -> Result<Self, String> {
+        let mut calendar = EventCalendar::default();
+
+        if let Some(start) = start {
+            (calendar.start_month, calendar.start_day) = start
+                .split_once('-')
+                .and_then(|(month, day)| Some((month.parse().ok()?, day.parse().ok()?)))
+                .filter(|(month, day)| is_valid_date(*month, *day))
+                .ok_or_else(|| {
+                    format!(
+                        "expected {EVENT_START_ENV_VAR}{year} to be a date like `12-01`, got `{start}`."
+                    )
+                })?;
+        }
+
+        if let Some(days) = days {
+            calendar.days = days
+                .parse()
+                .ok()
+                .filter(|x| (1..=LAST_DAY).contains(x))
+                .ok_or_else(|| {
+                    format!(
+                        "expected {EVENT_DAYS_ENV_VAR}{year} to be a number from 1 to 25, got `{days}`."
+                    )
+                })?;
+        }
+
+        Ok(calendar)
+    }
+
+    /// Returns the last day of the event.
+    pub fn last_day(&self) -> Day {
+        Day(self.days)
+    }
+
+    /// Returns the number of parts of the puzzle of a day: 1 for the last day, 2 otherwise.
+    pub fn part_count(&self, day: Day) -> u8 {
+        if day == self.last_day() {
+            1
         } else {
-            None
+            2
+        }
+    }
+
+    /// An iterator that yields every day of the event.
+    pub fn all_days(&self) -> AllDays {
+        AllDays {
+            current: 1,
+            last: self.days,
         }
     }
 }
 
+/// Whether `month` and `day` form a date, the 29th of february included.
+fn is_valid_date(month: u32, day: u32) -> bool {
+    let days_in_month = match month {
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return false,
+    };
+
+    (1..=days_in_month).contains(&day)
+}
+
+#[cfg(feature = "today")]
+impl EventCalendar {
+    /// Returns the day of the event of `year` on `date`, or `None` if the event is not running.
+    /// An event that runs into the next year belongs to the year it started in.
+    pub fn day_of(&self, year: Year, date: NaiveDate) -> Option<Day> {
+        let start = NaiveDate::from_ymd_opt(i32::from(year.0), self.start_month, self.start_day)?;
+        let offset = (date - start).num_days();
+
+        (0..i64::from(self.days))
+            .contains(&offset)
+            .then(|| Day::new(u8::try_from(offset + 1).ok()?))
+            .flatten()
+    }
+}
+
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -100,20 +256,198 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// The first year of Advent of Code.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Reads the year of a solution from its file name, e.g. `src/bin/2024-01.rs`.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_bin_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        assert!(
+            bytes.len() > start + 4 && bytes[start + 4] == b'-',
+            "expecting solution files to be named `<year>-<day>.rs`, e.g. `2024-01.rs`"
+        );
+
+        let mut year = 0;
+        let mut index = start;
+        while index < start + 4 {
+            assert!(
+                bytes[index].is_ascii_digit(),
+                "expecting solution files to be named `<year>-<day>.rs`, e.g. `2024-01.rs`"
+            );
+            year = year * 10 + (bytes[index] - b'0') as u16;
+            index += 1;
+        }
+
+        assert!(year >= FIRST_YEAR, "expecting a year of 2015 or later");
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Reads the year from the `AOC_YEAR` environment variable, which `.cargo/config.toml` sets to the default year.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The directory that holds the inputs, examples, puzzles and stored state of the year, e.g. `data/2024`.
+    pub fn data_dir(self) -> PathBuf {
+        Path::new("data").join(self.to_string())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A single puzzle, identified by its year and day.
+///
+/// # Display
+/// This value displays as the name of its solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, template::{PuzzleId, Year}};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), day!(8));
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The path of the solution, e.g. `src/bin/2024-01.rs`.
+    pub fn bin_path(self) -> PathBuf {
+        Path::new("src").join("bin").join(format!("{self}.rs"))
+    }
+
+    /// The path of the puzzle input, e.g. `data/2024/inputs/01.txt`.
+    pub fn input_path(self) -> PathBuf {
+        self.year
+            .data_dir()
+            .join("inputs")
+            .join(format!("{}.txt", self.day))
+    }
+
+    /// The path of the downloaded puzzle description, e.g. `data/2024/puzzles/01.md`.
+    pub fn puzzle_path(self) -> PathBuf {
+        self.year
+            .data_dir()
+            .join("puzzles")
+            .join(format!("{}.md", self.day))
+    }
+
+    /// Returns the number of parts of the puzzle: 1 for the last day of the event of its year, 2 otherwise.
+    pub fn part_count(self) -> u8 {
+        EventCalendar::for_year(self.year).part_count(self.day)
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of the current day of the Advent of Code server, `None` outside of the events.
+    /// The event of each year follows its own calendar, see [`EventCalendar::for_year`].
+    pub fn today() -> Option<Self> {
+        let date = server_now()?.date_naive();
+
+        [date.year(), date.year() - 1].into_iter().find_map(|year| {
+            let year = Year::new(u16::try_from(year).ok()?)?;
+            let day = EventCalendar::for_year(year).day_of(year, date)?;
+            Some(Self::new(year, day))
+        })
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields every day of the event of a year, from the 1st to the 25th for Advent of Code.
+pub fn all_days(year: Year) -> AllDays {
+    EventCalendar::for_year(year).all_days()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of an event, see [`EventCalendar::all_days`].
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
+    /// An iterator over all days of advent from the 1st to the 25th, regardless of the configured event.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self {
+            current: 1,
+            last: LAST_DAY,
+        }
     }
 }
 
@@ -121,10 +455,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and the last day is not above 25.
         let day = Day(self.current);
         self.current += 1;
 
@@ -150,15 +484,31 @@ macro_rules! day {
     }};
 }
 
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value of 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, EventCalendar, PuzzleId, Year};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(Year(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +537,119 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn counts_parts() {
+        assert_eq!(PuzzleId::new(Year(2024), Day(1)).part_count(), 2);
+        assert_eq!(PuzzleId::new(Year(2024), Day(24)).part_count(), 2);
+        assert_eq!(PuzzleId::new(Year(2024), Day(25)).part_count(), 1);
+    }
+
+    #[test]
+    fn follows_configured_event_days() {
+        let calendar = EventCalendar::from_vars(Year(2025), None, Some("12")).unwrap();
+
+        assert_eq!(calendar.last_day(), Day(12));
+        assert_eq!(calendar.part_count(Day(11)), 2);
+        assert_eq!(calendar.part_count(Day(12)), 1);
+        assert_eq!(calendar.all_days().last(), Some(Day(12)));
+        assert_eq!(calendar.all_days().count(), 12);
+    }
+
+    #[test]
+    fn keys_event_config_by_year() {
+        let lookup = |name: &str| (name == "AOC_EVENT_DAYS_2025").then(|| "12".to_string());
+
+        let short = EventCalendar::from_lookup(Year(2025), lookup).unwrap();
+        assert_eq!(short.last_day(), Day(12));
+
+        let advent = EventCalendar::from_lookup(Year(2024), lookup).unwrap();
+        assert_eq!(advent, EventCalendar::default());
+        assert_eq!(advent.part_count(Day(12)), 2);
+        assert_eq!(advent.all_days().count(), 25);
+    }
+
+    #[test]
+    fn validates_event_config() {
+        let from_vars = |start, days| EventCalendar::from_vars(Year(2025), start, days);
+
+        assert!(from_vars(Some("02-29"), None).is_ok());
+        assert!(from_vars(Some("02-30"), None).is_err());
+        assert!(from_vars(Some("13-01"), None).is_err());
+        assert!(from_vars(None, Some("0")).is_err());
+        assert!(from_vars(None, Some("26")).is_err());
+    }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn finds_event_days() {
+        use chrono::NaiveDate;
+
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let advent = EventCalendar::default();
+        assert_eq!(advent.day_of(Year(2024), date(2024, 12, 1)), Some(Day(1)));
+        assert_eq!(advent.day_of(Year(2024), date(2024, 12, 25)), Some(Day(25)));
+        assert_eq!(advent.day_of(Year(2024), date(2024, 12, 26)), None);
+        assert_eq!(advent.day_of(Year(2024), date(2024, 11, 30)), None);
+        assert_eq!(advent.day_of(Year(2023), date(2024, 12, 1)), None);
+
+        let short = EventCalendar {
+            days: 12,
+            ..EventCalendar::default()
+        };
+        assert_eq!(short.day_of(Year(2025), date(2025, 12, 12)), Some(Day(12)));
+        assert_eq!(short.day_of(Year(2025), date(2025, 12, 13)), None);
+
+        let new_year = EventCalendar {
+            start_month: 12,
+            start_day: 28,
+            days: 7,
+        };
+        assert_eq!(new_year.day_of(Year(2024), date(2025, 1, 3)), Some(Day(7)));
+        assert_eq!(new_year.day_of(Year(2024), date(2025, 1, 4)), None);
+        assert_eq!(new_year.day_of(Year(2025), date(2025, 1, 3)), None);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().ok(), Some(Year(2024)));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_years_from_bin_paths() {
+        assert_eq!(Year::__from_bin_path("src/bin/2023-07.rs"), Year(2023));
+        assert_eq!(
+            Year::__from_bin_path("C:\\aoc\\src\\bin\\2016-01.rs"),
+            Year(2016)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_bin_paths_without_year() {
+        Year::__from_bin_path("src/bin/01.rs");
+    }
+
+    #[test]
+    fn formats_puzzle_paths() {
+        let puzzle = PuzzleId::new(Year(2024), Day(1));
+        assert_eq!(puzzle.to_string(), "2024-01");
+        assert_eq!(
+            puzzle.bin_path(),
+            std::path::Path::new("src/bin/2024-01.rs")
+        );
+        assert_eq!(
+            puzzle.input_path(),
+            std::path::Path::new("data/2024/inputs/01.txt")
+        );
+        assert_eq!(
+            puzzle.puzzle_path(),
+            std::path::Path::new("data/2024/puzzles/01.md")
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,465 @@
+/// Named example inputs of a day together with their expected answers.
+/// Stored as `data/<year>/examples/<day>.json`, next to the example input files it refers to.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    sync::mpsc,
+};
+use tinyjson::JsonValue;
+
+use crate::template::in_process::SolutionFn;
+use crate::template::report::{PartReport, SolutionEvent};
+use crate::template::runner::RunContext;
+use crate::template::{PuzzleId, Year};
+
+static EXAMPLES_DIR_NAME: &str = "examples";
+
+/// The directory that holds the examples of a year, e.g. `data/2024/examples`.
+fn examples_dir(year: Year) -> PathBuf {
+    year.data_dir().join(EXAMPLES_DIR_NAME)
+}
+
+/// A single example input and the answers it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    /// Path of the input file, relative to `data/<year>/examples`.
+    pub file: String,
+    /// Expected answers of both parts.
+    pub expected: [Expected; 2],
+}
+
+/// What a part of an example is expected to produce.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Expected {
+    /// The part is not checked, stored as `null`.
+    #[default]
+    Unchecked,
+    /// The part is expected to produce this answer.
+    Answer(String),
+    /// The part is expected to produce no answer, stored as `false`.
+    NoAnswer,
+}
+
+impl Expected {
+    /// The answer a checked part is expected to produce, `None` for unchecked parts.
+    pub fn answer(&self) -> Option<Option<&str>> {
+        match self {
+            Expected::Unchecked => None,
+            Expected::Answer(x) => Some(Some(x)),
+            Expected::NoAnswer => Some(None),
+        }
+    }
+}
+
+/// Represents the examples of a single day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Examples {
+    pub data: Vec<Example>,
+}
+
+/// The outcome of checking a single part of an example.
+/// An answer of `None` means that the part produced no answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartCheck {
+    pub part: u8,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl PartCheck {
+    pub fn is_ok(&self) -> bool {
+        self.actual == self.expected
+    }
+}
+
+/// Format an answer of a [`PartCheck`] for display.
+pub fn format_answer(answer: Option<&str>) -> String {
+    answer.map_or("no answer".into(), |x| format!("`{x}`"))
+}
+
+impl Example {
+    /// Get the expected answer of a part, 1 or 2.
+    pub fn expected(&self, part: u8) -> &Expected {
+        &self.expected[usize::from(part - 1)]
+    }
+
+    /// The part to run for this example, if only one part is checked.
+    pub fn part_filter(&self) -> Option<u8> {
+        match (self.expected(1), self.expected(2)) {
+            (Expected::Unchecked, Expected::Unchecked) => None,
+            (_, Expected::Unchecked) => Some(1),
+            (Expected::Unchecked, _) => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Whether the example has parts to check.
+    pub fn is_checked(&self) -> bool {
+        self.expected.iter().any(|x| *x != Expected::Unchecked)
+    }
+
+    pub fn path(&self, year: Year) -> PathBuf {
+        examples_dir(year).join(&self.file)
+    }
+
+    pub fn read_input(&self, year: Year) -> Result<String, String> {
+        fs::read_to_string(self.path(year))
+            .map_err(|e| format!("could not read example file `{}`: {e}", self.file))
+    }
+
+    /// Compare the answers a solution reported for this example against the expected answers.
+    pub fn check(&self, reports: &[PartReport]) -> Vec<PartCheck> {
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                let expected = self.expected(part).answer()?;
+                let actual = reports
+                    .iter()
+                    .find(|r| r.part == part)
+                    .and_then(|r| r.answer.clone());
+
+                Some(PartCheck {
+                    part,
+                    expected: expected.map(Into::into),
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Examples {
+    /// The JSON file of the examples of a puzzle, e.g. `data/2024/examples/01.json`.
+    pub fn file_path(puzzle: PuzzleId) -> PathBuf {
+        examples_dir(puzzle.year).join(format!("{}.json", puzzle.day))
+    }
+
+    /// Dehydrate the examples of a puzzle to its JSON file.
+    pub fn store_file(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(examples_dir(puzzle.year))?;
+        let mut file = fs::File::create(Examples::file_path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the examples of a puzzle from its JSON file. If not present, returns no examples.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(Examples::file_path(puzzle)) {
+            Ok(contents) => Examples::try_from(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Examples::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Add an example, replacing an existing example of the same name.
+    pub fn set(&mut self, example: Example) {
+        match self.data.iter_mut().find(|x| x.name == example.name) {
+            Some(existing) => *existing = example,
+            None => self.data.push(example),
+        }
+    }
+}
+
+/// Run all examples of a puzzle through `solution` in the current process and panic if an answer does not match.
+/// Called by the test that the [`solution!`](crate::solution) macro generates for each day.
+/// A missing manifest fails the test, so days without examples do not pass unnoticed.
+pub fn test_examples(puzzle: PuzzleId, solution: SolutionFn) {
+    let path = Examples::file_path(puzzle);
+
+    assert!(
+        path.exists(),
+        "No examples for {puzzle}: expected a manifest at \"{}\", see `cargo examples --extract`.",
+        path.display()
+    );
+
+    let examples = Examples::read_from_file(puzzle).unwrap_or_else(|e| {
+        panic!("Failed to read examples of {puzzle}: {e}");
+    });
+
+    if !examples.data.iter().any(Example::is_checked) {
+        eprintln!(
+            "No examples with expected answers for {puzzle}, nothing was checked. Add them to \"{}\".",
+            path.display()
+        );
+    }
+
+    let mut failures: Vec<String> = vec![];
+
+    for example in examples.data.iter().filter(|x| x.is_checked()) {
+        let input = example
+            .read_input(puzzle.year)
+            .unwrap_or_else(|e| panic!("{e}"));
+
+        let (tx, rx) = mpsc::channel();
+        solution(
+            &input,
+            &RunContext::in_process(puzzle, None, example.part_filter(), tx),
+        );
+
+        let reports: Vec<PartReport> = rx
+            .try_iter()
+            .filter_map(|event| match event {
+                SolutionEvent::Report(report) => Some(report),
+                _ => None,
+            })
+            .collect();
+
+        for check in example.check(&reports).iter().filter(|x| !x.is_ok()) {
+            failures.push(format!(
+                "{} (part {}): expected {}, got {}",
+                example.name,
+                check.part,
+                format_answer(check.expected.as_deref()),
+                format_answer(check.actual.as_deref())
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Examples failed:\n{}",
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Examples> for JsonValue {
+    fn from(value: Examples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Examples {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Examples {
+            data: json_data
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        for (key, expected) in ["part_1", "part_2"].iter().zip(&value.expected) {
+            map.insert(
+                (*key).into(),
+                match expected {
+                    Expected::Unchecked => JsonValue::Null,
+                    Expected::Answer(x) => JsonValue::String(x.clone()),
+                    Expected::NoAnswer => JsonValue::Boolean(false),
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.name to be a string.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        Ok(Example {
+            name: name.clone(),
+            file: file.clone(),
+            expected: [
+                expected_from_json(json, "part_1")?,
+                expected_from_json(json, "part_2")?,
+            ],
+        })
+    }
+}
+
+/// Read an optional expected answer at `key`. Numbers are accepted for convenience, `false` expects no answer.
+fn expected_from_json(json: &HashMap<String, JsonValue>, key: &str) -> Result<Expected, String> {
+    match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(Expected::Unchecked),
+        Some(JsonValue::Boolean(false)) => Ok(Expected::NoAnswer),
+        Some(JsonValue::String(x)) => Ok(Expected::Answer(x.clone())),
+        Some(JsonValue::Number(x)) if x.fract() == 0.0 => Ok(Expected::Answer(format!("{x:.0}"))),
+        Some(_) => Err(format!(
+            "Expected example.{key} to be null, false, a string or an integer."
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{day, template::report::PartReport};
+
+    use super::{Example, Examples, Expected, PartCheck};
+
+    fn example(part_1: Option<&str>, part_2: Option<&str>) -> Example {
+        let expected =
+            |x: Option<&str>| x.map_or(Expected::Unchecked, |x| Expected::Answer(x.into()));
+
+        Example {
+            name: "example".into(),
+            file: "01.txt".into(),
+            expected: [expected(part_1), expected(part_2)],
+        }
+    }
+
+    fn report(part: u8, answer: &str) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            answer: Some(answer.into()),
+            duration: Duration::from_nanos(1),
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    mod deserialization {
+        use crate::template::examples::{Examples, Expected};
+
+        #[test]
+        fn handles_json_examples() {
+            let json = r#"{ "data": [{ "name": "small", "file": "01.txt", "part_1": "11", "part_2": 31 }, { "name": "large", "file": "01-large.txt", "part_2": false }] }"#.to_string();
+            let examples = Examples::try_from(json).unwrap();
+            assert_eq!(examples.data.len(), 2);
+            assert_eq!(examples.data[0].expected(1), &Expected::Answer("11".into()));
+            assert_eq!(examples.data[0].expected(2), &Expected::Answer("31".into()));
+            assert_eq!(examples.data[1].file, "01-large.txt");
+            assert_eq!(examples.data[1].expected(1), &Expected::Unchecked);
+            assert_eq!(examples.data[1].expected(2), &Expected::NoAnswer);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_invalid_answers() {
+            let json =
+                r#"{ "data": [{ "name": "small", "file": "01.txt", "part_1": 1.5 }] }"#.to_string();
+            Examples::try_from(json).unwrap();
+        }
+    }
+
+    #[test]
+    fn roundtrips_examples() {
+        let mut examples = Examples::default();
+        examples.set(example(Some("11"), None));
+        examples.set(Example {
+            name: "no-answer".into(),
+            file: "01-2.txt".into(),
+            expected: [Expected::Unchecked, Expected::NoAnswer],
+        });
+        let json = tinyjson::JsonValue::from(examples.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Examples::try_from(json).unwrap();
+        assert_eq!(parsed.data, examples.data);
+    }
+
+    #[test]
+    fn replaces_examples_by_name() {
+        let mut examples = Examples::default();
+        examples.set(example(Some("11"), None));
+        examples.set(example(Some("12"), None));
+        assert_eq!(examples.data.len(), 1);
+        assert_eq!(examples.data[0].expected(1), &Expected::Answer("12".into()));
+    }
+
+    #[test]
+    fn filters_parts() {
+        assert_eq!(example(Some("1"), Some("2")).part_filter(), None);
+        assert_eq!(example(Some("1"), None).part_filter(), Some(1));
+        assert_eq!(example(None, Some("2")).part_filter(), Some(2));
+        assert!(!example(None, None).is_checked());
+    }
+
+    #[test]
+    fn checks_reports() {
+        let checks = example(Some("11"), Some("31")).check(&[report(1, "11"), report(2, "30")]);
+        assert_eq!(
+            checks,
+            vec![
+                PartCheck {
+                    part: 1,
+                    expected: Some("11".into()),
+                    actual: Some("11".into())
+                },
+                PartCheck {
+                    part: 2,
+                    expected: Some("31".into()),
+                    actual: Some("30".into())
+                }
+            ]
+        );
+        assert!(checks[0].is_ok());
+        assert!(!checks[1].is_ok());
+
+        let checks = example(Some("11"), None).check(&[]);
+        assert_eq!(checks[0].actual, None);
+    }
+
+    #[test]
+    fn checks_parts_without_answer() {
+        let example = Example {
+            expected: [Expected::Answer("11".into()), Expected::NoAnswer],
+            ..example(None, None)
+        };
+        assert!(example.is_checked());
+        assert_eq!(example.part_filter(), None);
+
+        let checks = example.check(&[report(1, "11")]);
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(PartCheck::is_ok));
+
+        let checks = example.check(&[report(1, "11"), report(2, "0")]);
+        assert!(!checks[1].is_ok());
+    }
+}
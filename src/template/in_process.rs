@@ -0,0 +1,29 @@
+/// Registry of solutions that are linked into the main binary when the `in_process` feature is enabled.
+use std::sync::OnceLock;
+
+use crate::template::{runner::RunContext, PuzzleId};
+
+/// Entry point of a solution, generated by the [`solution!`](crate::solution) macro.
+pub type SolutionFn = fn(&str, &RunContext);
+
+static SOLUTIONS: OnceLock<Vec<(PuzzleId, SolutionFn)>> = OnceLock::new();
+
+/// Register the solutions linked into the current binary.
+/// Once registered, `all` and `time` run them in-process instead of spawning a `cargo run` per day.
+pub fn register(solutions: Vec<(PuzzleId, SolutionFn)>) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Whether solutions were registered for in-process runs.
+pub fn is_enabled() -> bool {
+    SOLUTIONS.get().is_some()
+}
+
+/// Get the registered solution of a puzzle.
+pub fn get(puzzle: PuzzleId) -> Option<SolutionFn> {
+    SOLUTIONS
+        .get()?
+        .iter()
+        .find(|(p, _)| *p == puzzle)
+        .map(|(_, solution)| *solution)
+}
@@ -0,0 +1,234 @@
+/// Heap statistics of solution parts, collected by a counting global allocator.
+/// The allocator is installed by the `solution!` macro when the `memory` feature is enabled.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tinyjson::JsonValue;
+
+static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Heap usage of a single execution of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    /// Sum of the bytes of all allocations.
+    pub total_bytes: u64,
+    /// Number of allocations.
+    pub allocations: u64,
+}
+
+/// A global allocator that counts the allocations of the system allocator.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+        TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: all calls are forwarded to the system allocator, only counters are updated in addition.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            CountingAlloc::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            CountingAlloc::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CountingAlloc::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CountingAlloc::record_dealloc(layout.size());
+            CountingAlloc::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether solutions in this build allocate through [`CountingAlloc`].
+/// The `dhat-heap` feature replaces the allocator, and in-process runs share the allocator of the runner.
+pub const fn is_enabled() -> bool {
+    cfg!(all(
+        feature = "memory",
+        not(feature = "dhat-heap"),
+        not(feature = "in_process")
+    ))
+}
+
+/// Run `func` and collect the heap statistics of its execution, if [`is_enabled`].
+/// Allocations are counted process-wide, so this should not run concurrently with other work.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !is_enabled() {
+        return (func(), None);
+    }
+
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+    let total_bytes = TOTAL_BYTES.load(Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = MemoryStats {
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed) - baseline,
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed) - total_bytes,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+
+    (result, Some(stats))
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: get("peak_bytes")?,
+            total_bytes: get("total_bytes")?,
+            allocations: get("allocations")?,
+        })
+    }
+}
+
+/// Read optional memory stats at `key`.
+pub fn memory_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<MemoryStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => MemoryStats::try_from(v).map(Some),
+    }
+}
+
+/// Convert optional memory stats to JSON.
+pub fn memory_to_json(stats: Option<&MemoryStats>) -> JsonValue {
+    match stats {
+        Some(x) => JsonValue::from(x),
+        None => JsonValue::Null,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, measure, MemoryStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn skips_measuring_without_allocator() {
+        // NOTE: the test harness does not install the counting allocator.
+        let (result, stats) = measure(|| vec![1, 2, 3].len());
+        assert_eq!(result, 3);
+        assert_eq!(stats.is_some(), super::is_enabled());
+    }
+
+    #[test]
+    fn roundtrips_memory_stats() {
+        let stats = MemoryStats {
+            peak_bytes: 1024,
+            total_bytes: 4096,
+            allocations: 12,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(MemoryStats::try_from(&json), Ok(stats));
+    }
+}
@@ -2,45 +2,85 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod examples;
+pub mod in_process;
+pub mod memory;
 pub mod runner;
 
+pub use bench::{BenchConfig, Statistic};
+pub use compare::{RegressionConfig, TableFormat};
 pub use day::*;
+pub use selection::DaySelection;
+pub use timeout::TimeoutConfig;
 
+mod answers;
+mod aoc_client;
+mod bench;
+mod cache;
+mod charts;
+mod compare;
 mod day;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod selection;
+mod submissions;
+mod timeout;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2024/examples/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(puzzle.year.data_dir())
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.year.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The year is taken from the file name of the solution, e.g. `src/bin/2024-01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The puzzle of the last day of an event only has a first part, `cargo scaffold` writes `solution!(25, 1)` for it.
+///
+/// Passing `parse` as second parameter (e.g. `solution!(10, parse)`) runs a `parse` function on the input first
+/// and passes a reference to its output to both parts. The parse step is timed separately from the parts.
+/// A single part can be selected with a third parameter, e.g. `solution!(10, parse, 1)`.
+///
+/// In test builds, the macro also generates a test that checks the examples of the day, see [`examples`].
 #[macro_export]
 macro_rules! solution {
+    ($day:expr, parse) => {
+        $crate::solution!(@impl $day, parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@impl $day, parse, [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@impl $day, parse, [part_two, 2]);
+    };
     ($day:expr) => {
         $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
     };
@@ -51,18 +91,59 @@ macro_rules! solution {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
 
+    (@impl $day:expr, parse, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        /// Run all parts of this solution on `input`.
+        #[doc(hidden)]
+        pub fn __run(input: &str, ctx: &$crate::template::runner::RunContext) {
+            use $crate::template::runner::*;
+            let parsed = run_parse(parse, input, ctx);
+            $( run_part($func, &parsed, ctx, $part); )*
+        }
+    };
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        /// Run all parts of this solution on `input`.
+        #[doc(hidden)]
+        pub fn __run(input: &str, ctx: &$crate::template::runner::RunContext) {
+            use $crate::template::runner::*;
+            $( run_part($func, input, ctx, $part); )*
+        }
+    };
+
+    (@setup $day:expr) => {
+        /// The year of the current solution.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_bin_path(file!());
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::runner::read_input(PUZZLE);
+            __run(&input, &$crate::template::runner::RunContext::from_env(PUZZLE));
         }
+
+        /// Checks the answers of all examples in `data/<year>/examples/<day>.json`.
+        #[cfg(test)]
+        mod __examples {
+            #[test]
+            fn examples() {
+                $crate::template::examples::test_examples(super::PUZZLE, super::__run);
+            }
+        }
+
+        // NOTE: solutions linked into the in-process runner share its allocator.
+        #[cfg(all(feature = "dhat-heap", not(feature = "in_process")))]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        #[cfg(all(feature = "memory", not(feature = "dhat-heap"), not(feature = "in_process")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc = $crate::template::memory::CountingAlloc;
     };
 }
@@ -0,0 +1,283 @@
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// Whether the text before the block mentions an example, e.g. "For example:".
+    pub follows_example: bool,
+}
+
+impl CodeBlock {
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+}
+
+/// An example input picked from the code blocks of a puzzle, with the answers expected for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedExample {
+    /// Index into [`Puzzle::blocks`].
+    pub block: usize,
+    pub expected: [Option<String>; 2],
+}
+
+/// The content of a puzzle description, split into code blocks and the answers given in the text.
+/// Descriptions are stored as markdown: example inputs are fenced code blocks and answers are emphasized code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub blocks: Vec<CodeBlock>,
+    /// The last emphasized value of each part, which is the answer for the example in almost all puzzles.
+    pub answers: [Option<String>; 2],
+}
+
+fn is_part_two_heading(line: &str) -> bool {
+    line.contains("--- Part Two ---")
+}
+
+impl Puzzle {
+    pub fn parse(markdown: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+        let mut part = 1;
+        let mut block: Option<Vec<&str>> = None;
+        let mut last_text = "";
+
+        for line in markdown.lines() {
+            if line.trim_start().starts_with("```") {
+                match block.take() {
+                    Some(mut lines) => {
+                        while lines.last().is_some_and(|x| x.trim().is_empty()) {
+                            lines.pop();
+                        }
+
+                        let mut content = lines.join("\n");
+                        content.push('\n');
+
+                        puzzle.blocks.push(CodeBlock {
+                            part,
+                            content,
+                            follows_example: last_text.to_lowercase().contains("example"),
+                        });
+                        last_text = "";
+                    }
+                    None => block = Some(vec![]),
+                }
+                continue;
+            }
+
+            if let Some(lines) = &mut block {
+                lines.push(line);
+                continue;
+            }
+
+            if is_part_two_heading(line) {
+                part = 2;
+            }
+
+            if !line.trim().is_empty() {
+                last_text = line;
+                if let Some(answer) = emphasized_values(line).last() {
+                    puzzle.answers[usize::from(part) - 1] = Some(answer.clone());
+                }
+            }
+        }
+
+        puzzle
+    }
+
+    /// Pick the example input of a part: the first block after text that mentions an example.
+    /// Part one falls back to its longest block. Part two often only illustrates the first example,
+    /// so it has no fallback.
+    pub fn example_block(&self, part: u8) -> Option<usize> {
+        let candidates = || {
+            self.blocks
+                .iter()
+                .enumerate()
+                .filter(move |(_, block)| block.part == part)
+        };
+
+        candidates()
+            .find(|(_, block)| block.follows_example)
+            .or_else(|| {
+                (part == 1)
+                    .then(|| candidates().max_by_key(|(_, block)| block.line_count()))
+                    .flatten()
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// Pick the example inputs of the puzzle, or use `block` as the only example.
+    /// Part two gets a separate example if it introduces a new input.
+    pub fn extract_examples(&self, block: Option<usize>) -> Vec<ExtractedExample> {
+        if let Some(block) = block {
+            return vec![ExtractedExample {
+                block,
+                expected: self.answers.clone(),
+            }];
+        }
+
+        let Some(part_1) = self.example_block(1) else {
+            return vec![];
+        };
+
+        match self.example_block(2) {
+            Some(part_2) if self.blocks[part_2].content != self.blocks[part_1].content => vec![
+                ExtractedExample {
+                    block: part_1,
+                    expected: [self.answers[0].clone(), None],
+                },
+                ExtractedExample {
+                    block: part_2,
+                    expected: [None, self.answers[1].clone()],
+                },
+            ],
+            _ => vec![ExtractedExample {
+                block: part_1,
+                expected: self.answers.clone(),
+            }],
+        }
+    }
+}
+
+/// Find emphasized code in a line of markdown, e.g. `` `*11*` `` or `` *`11`* ``.
+fn emphasized_values(line: &str) -> Vec<String> {
+    let segments: Vec<&str> = line.split('`').collect();
+
+    // NOTE: with balanced backticks, every odd segment is the content of a code span.
+    (1..segments.len().saturating_sub(1))
+        .step_by(2)
+        .filter_map(|index| {
+            let code = segments[index];
+
+            let value = match code.strip_prefix('*').and_then(|x| x.strip_suffix('*')) {
+                Some(value) => value,
+                None if segments[index - 1].ends_with('*')
+                    && segments[index + 1].starts_with('*') =>
+                {
+                    code
+                }
+                None => return None,
+            };
+
+            (!value.is_empty() && !value.contains('*')).then(|| value.to_string())
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized_values, ExtractedExample, Puzzle};
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+There's just one problem: by holding the two lists up *side by side* (your puzzle input), it quickly becomes clear that the lists aren't very similar. Maybe you can help them reconcile their lists?
+
+For example:
+
+```
+3   4
+4   3
+2   5
+
+```
+
+Pair up the numbers and measure how far apart they are. In the example above, this is `2 + 1 + 0 = *3*`, so the total distance is `*3*`!
+
+Your puzzle answer was `1234`.
+
+\--- Part Two ---
+----------
+
+This time, you'll need to figure out exactly how often each number from the left list appears in the right list. Here are the same example lists again:
+
+```
+3   4
+4   3
+2   5
+
+```
+
+The first number is `3`:
+
+```
+3 * 1
+
+```
+
+So, for these example lists, the similarity score at the end of this process is `*31*`.
+";
+
+    #[test]
+    fn parses_code_blocks() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.blocks.len(), 3);
+        assert_eq!(puzzle.blocks[0].content, "3   4\n4   3\n2   5\n");
+        assert_eq!(puzzle.blocks[0].part, 1);
+        assert!(puzzle.blocks[0].follows_example);
+        assert_eq!(puzzle.blocks[1].part, 2);
+        assert!(puzzle.blocks[1].follows_example);
+        assert!(!puzzle.blocks[2].follows_example);
+    }
+
+    #[test]
+    fn finds_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.answers, [Some("3".into()), Some("31".into())]);
+    }
+
+    #[test]
+    fn picks_example_blocks() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.example_block(1), Some(0));
+        assert_eq!(puzzle.example_block(2), Some(1));
+
+        let puzzle = Puzzle::parse(PUZZLE.split("\\--- Part Two").next().unwrap());
+        assert_eq!(puzzle.example_block(2), None);
+        assert_eq!(puzzle.answers[1], None);
+    }
+
+    #[test]
+    fn extracts_shared_examples() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(
+            puzzle.extract_examples(None),
+            vec![ExtractedExample {
+                block: 0,
+                expected: [Some("3".into()), Some("31".into())]
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_separate_examples() {
+        let markdown = "For example:\n```\n1\n```\nThe answer is `*3*`.\n\\--- Part Two ---\nA new example:\n```\n2\n```\nThe answer is `*31*`.\n";
+        let puzzle = Puzzle::parse(markdown);
+
+        assert_eq!(
+            puzzle.extract_examples(None),
+            vec![
+                ExtractedExample {
+                    block: 0,
+                    expected: [Some("3".into()), None]
+                },
+                ExtractedExample {
+                    block: 1,
+                    expected: [None, Some("31".into())]
+                }
+            ]
+        );
+
+        assert_eq!(puzzle.extract_examples(Some(1))[0].block, 1);
+    }
+
+    #[test]
+    fn finds_emphasized_values() {
+        assert_eq!(emphasized_values("this is `*11*`."), vec!["11"]);
+        assert_eq!(emphasized_values("a *`1`* and `*2*`"), vec!["1", "2"]);
+        assert_eq!(emphasized_values("`2 + 1 = *3*`"), Vec::<String>::new());
+        assert_eq!(emphasized_values("*side by side*"), Vec::<String>::new());
+    }
+}
@@ -1,11 +1,25 @@
-/// Module that updates the readme me with timing information.
+/// Module that updates the readme me with timing information and earned stars.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::answers::Answers;
+use crate::template::bench::{format_nanos, Statistic};
+use crate::template::memory::format_bytes;
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{Day, EventCalendar, PuzzleId, Year};
 
+/// Marker of a benchmark table without a year, as written by earlier versions of the template.
 static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table ";
+static STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
+static README_PATH: &str = "README.md";
+static CONFIG_FILE_PATH: &str = "./data/readme_table.json";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,18 +39,97 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
+/// An optional column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Duration of the parse step. Only shown if at least one solution has a separate parse step.
+    Parse,
+    /// Number of samples of both parts.
+    Samples,
+    /// Peak heap usage of both parts, as measured by `cargo time --memory`.
+    Memory,
+    /// Standard deviation, shown next to each duration.
+    StdDev,
+    /// Share of a day in the total duration.
+    Share,
+    /// Earned stars of a day.
+    Stars,
+}
+
+/// Order of the rows of the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest days first.
+    Slowest,
+}
+
+/// Layout of the benchmark table, read from `data/readme_table.json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    /// Level of the table heading, from 1 to 6.
+    pub heading_level: u8,
+    /// The file that contains the table.
+    pub path: String,
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        ReadmeConfig {
+            columns: vec![Column::Parse],
+            sort: SortOrder::default(),
+            heading_level: 2,
+            path: README_PATH.into(),
+        }
+    }
+}
+
+impl ReadmeConfig {
+    /// Read the table layout from its config file. If not present, returns the default layout.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => ReadmeConfig::try_from(s).map_err(|e| format!("{CONFIG_FILE_PATH}: {e}")),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(ReadmeConfig::default()),
+            Err(e) => Err(format!("{CONFIG_FILE_PATH}: {e}")),
+        }
+    }
+
+    fn has_column(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+/// Get the link to the solution of a puzzle, relative to the file at `target`.
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId, target: &str) -> String {
+    let depth = Path::new(target).parent().map_or(0, |dir| {
+        dir.components()
+            .filter(|x| matches!(x, Component::Normal(_)))
+            .count()
+    });
+
+    if depth == 0 {
+        format!("./src/bin/{puzzle}.rs")
+    } else {
+        format!("{}src/bin/{puzzle}.rs", "../".repeat(depth))
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Get the marker of the benchmark table of a year.
+fn year_marker(year: Year) -> String {
+    format!("{MARKER_PREFIX}{year} --->")
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,78 +145,522 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_part(
+    part: Option<&PartTiming>,
+    statistic: Statistic,
+    is_timed_out: bool,
+    with_stddev: bool,
+) -> String {
+    match part {
+        Some(part) => match part.stats.as_ref().filter(|_| with_stddev) {
+            Some(stats) => format!(
+                "{} ± {}",
+                format_nanos(part.nanos_by(statistic)),
+                format_nanos(stats.stddev)
+            ),
+            None => format_nanos(part.nanos_by(statistic)),
+        },
+        None if is_timed_out => "timed out".into(),
+        None => "-".into(),
+    }
+}
+
+fn format_samples(timing: &Timing) -> String {
+    let format = |part: Option<&PartTiming>| part.map_or("-".into(), |x| x.samples.to_string());
+    format!(
+        "{} / {}",
+        format(timing.part_1.as_ref()),
+        format(timing.part_2.as_ref())
+    )
+}
+
+fn format_memory(timing: &Timing) -> String {
+    let format = |part: Option<&PartTiming>| {
+        part.and_then(|x| x.memory)
+            .map_or("-".into(), |x| format_bytes(x.peak_bytes))
+    };
+    format!(
+        "{} / {}",
+        format(timing.part_1.as_ref()),
+        format(timing.part_2.as_ref())
+    )
+}
+
+fn construct_table(
+    config: &ReadmeConfig,
+    year: Year,
+    mut timings: Timings,
+    stars: &[(Day, [bool; 2])],
+    total_millis: f64,
+    statistic: Statistic,
+) -> String {
+    let prefix = "#".repeat(usize::from(config.heading_level));
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let header = if statistic == Statistic::default() {
+        format!("{prefix} {year} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks ({statistic})")
+    };
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    // NOTE: only show a parse column if at least one solution has a separate parse step.
+    let has_parse =
+        config.has_column(Column::Parse) && timings.data.iter().any(|t| t.parse.is_some());
+    let with_stddev = config.has_column(Column::StdDev);
+
+    if config.sort == SortOrder::Slowest {
+        timings.data.sort_by(|a, b| {
+            b.total_nanos_by(statistic)
+                .total_cmp(&a.total_nanos_by(statistic))
+        });
+    }
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    for (column, name) in [
+        (Column::Samples, "Samples"),
+        (Column::Memory, "Memory"),
+        (Column::Share, "Share"),
+        (Column::Stars, "Stars"),
+    ] {
+        if config.has_column(column) {
+            columns.push(name);
+        }
+    }
+
+    let marker = year_marker(year);
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
+
+    for timing in &timings.data {
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day), &config.path);
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        if has_parse {
+            cells.push(format!(
+                "`{}`",
+                format_part(timing.parse.as_ref(), statistic, false, with_stddev)
+            ));
+        }
+
+        for part in [1, 2] {
+            cells.push(format!(
+                "`{}`",
+                format_part(
+                    timing.part(part),
+                    statistic,
+                    timing.timed_out == Some(part),
+                    with_stddev
+                )
+            ));
+        }
+
+        if config.has_column(Column::Samples) {
+            cells.push(format_samples(timing));
+        }
+
+        if config.has_column(Column::Memory) {
+            cells.push(format_memory(timing));
+        }
+
+        if config.has_column(Column::Share) {
+            // NOTE: the share is undefined when nothing was timed, e.g. for days that all timed out.
+            if total_millis > 0.0 {
+                let share = timing.total_nanos_by(statistic) / 1_000_000_f64 / total_millis;
+                cells.push(format!("{:.1}%", share * 100.0));
+            } else {
+                cells.push("-".into());
+            }
+        }
+
+        if config.has_column(Column::Stars) {
+            let count = stars
+                .iter()
+                .find(|x| x.0 == timing.day)
+                .map_or(0, |x| x.1.iter().filter(|x| **x).count());
+            cells.push("⭐".repeat(count));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Find where the table of a year goes: its existing table, a table without a year, or after the last table of another year.
+fn locate_year_table(readme: &str, year: Year) -> Result<TablePosition, Error> {
+    let marker = year_marker(year);
+    if readme.contains(&marker) {
+        return locate_table(readme, &marker);
+    }
+
+    // NOTE: a table without a year was written before multi-year support and is taken over by the first year timed.
+    if readme.contains(MARKER) {
+        return locate_table(readme, MARKER);
+    }
+
+    let pos = readme
+        .rfind(MARKER_PREFIX)
+        .and_then(|start| readme[start..].find("--->").map(|end| start + end + 4))
+        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+
+    Ok(TablePosition {
+        pos_start: pos,
+        pos_end: pos,
+    })
+}
+
+fn update_content(
+    s: &mut String,
+    config: &ReadmeConfig,
+    year: Year,
+    timings: Timings,
+    stars: &[(Day, [bool; 2])],
+    total_millis: f64,
+    statistic: Statistic,
+) -> Result<(), Error> {
+    let positions = locate_year_table(s, year)?;
+    let mut table = construct_table(config, year, timings, stars, total_millis, statistic);
+    if positions.pos_start == positions.pos_end {
+        table.insert_str(0, "\n\n");
+    }
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+pub fn update(
+    year: Year,
+    timings: Timings,
+    statistic: Statistic,
+    config: &ReadmeConfig,
+) -> Result<(), Error> {
+    let mut readme = String::from_utf8_lossy(&fs::read(&config.path)?).to_string();
+    let total_millis = timings.total_millis_by(statistic);
+
+    let stars = if config.has_column(Column::Stars) {
+        earned_stars(
+            year,
+            &read_shown_stars(year),
+            &Answers::read_from_file(year).map_err(Error::Parser)?,
+            &timings,
+        )
+    } else {
+        vec![]
+    };
+
+    update_content(
+        &mut readme,
+        config,
+        year,
+        timings,
+        &stars,
+        total_millis,
+        statistic,
+    )?;
+    fs::write(&config.path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Read the stars shown in an existing stars table.
+fn parse_stars_table(table: &str) -> Vec<(Day, [bool; 2])> {
+    table
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let [_, label, part_1, part_2, ..] = cells.as_slice() else {
+                return None;
+            };
+
+            let day = label
+                .strip_prefix("[Day ")
+                .or_else(|| label.strip_prefix("Day "))?
+                .split(|c: char| !c.is_ascii_digit())
+                .next()?
+                .parse()
+                .ok()
+                .and_then(Day::new)?;
+
+            Some((day, [part_1.contains('⭐'), part_2.contains('⭐')]))
+        })
+        .collect()
+}
+
+/// Get the stars earned for both parts of each day, skipping days without stars.
+/// Stars are read from the answer registry, days with stored timings for both parts count as fully solved.
+/// Stars that are already `shown` in the readme are kept, since they can't be lost.
+/// The last day of the event of `year` has a single part, its second star is earned together with all other stars.
+fn earned_stars(
+    year: Year,
+    shown: &[(Day, [bool; 2])],
+    answers: &Answers,
+    timings: &Timings,
+) -> Vec<(Day, [bool; 2])> {
+    let calendar = EventCalendar::for_year(year);
+
+    let mut stars: Vec<(Day, [bool; 2])> = calendar
+        .all_days()
+        .map(|day| {
+            let is_complete = timings.is_day_complete(year, day);
+            let shown = shown
+                .iter()
+                .find(|x| x.0 == day)
+                .map_or([false; 2], |x| x.1);
+            let stars = [1, 2].map(|part| {
+                shown[usize::from(part - 1)]
+                    || (is_complete && part <= calendar.part_count(day))
+                    || answers.get(day, part).is_some()
+            });
+            (day, stars)
+        })
+        .collect();
+
+    let has_other_stars = stars
+        .iter()
+        .all(|(day, stars)| calendar.part_count(*day) == 1 || *stars == [true; 2]);
+
+    for (day, stars) in &mut stars {
+        if calendar.part_count(*day) == 1 && stars[0] && has_other_stars {
+            stars[1] = true;
+        }
+    }
+
+    stars.retain(|(_, stars)| stars.contains(&true));
+    stars
+}
+
+/// Read the year of an existing stars table from its header, e.g. `## 2024 Results`.
+fn parse_stars_year(table: &str) -> Option<Year> {
+    table
+        .lines()
+        .find(|line| line.starts_with('#'))?
+        .trim_start_matches('#')
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Read the stars shown in an existing stars table, if it belongs to `year`.
+/// A table without a year is assumed to belong to it.
+fn parse_shown_stars(table: &str, year: Year) -> Vec<(Day, [bool; 2])> {
+    match parse_stars_year(table) {
+        Some(x) if x != year => vec![],
+        _ => parse_stars_table(table),
+    }
+}
+
+fn construct_stars_table(prefix: &str, stars: &[(Day, [bool; 2])], year: Year) -> String {
+    let header = format!("{prefix} {year} Results");
+
+    let mut lines: Vec<String> = vec![STARS_MARKER.into(), header, String::new()];
+
+    lines.push("| Day | Part 1 | Part 2 |".into());
+    lines.push("| :---: | :---: | :---: |".into());
+
+    for (day, stars) in stars {
+        let label = format!(
+            "[Day {}](https://adventofcode.com/{year}/day/{})",
+            day.into_inner(),
+            day.into_inner()
+        );
+
+        let [part_1, part_2] = stars.map(|x| if x { "⭐" } else { " " });
+        lines.push(format!("| {label} | {part_1} | {part_2} |"));
+    }
+
+    lines.push(STARS_MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_stars_content(
+    s: &mut String,
+    answers: &Answers,
+    timings: &Timings,
+    year: Year,
+) -> Result<(), Error> {
+    let positions = locate_table(s, STARS_MARKER)?;
+    let shown = parse_shown_stars(&s[positions.pos_start..positions.pos_end], year);
+    let stars = earned_stars(year, &shown, answers, timings);
+    let table = construct_stars_table("##", &stars, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
+/// Read the stars of a year shown in the stars table of the readme, if it has one.
+fn read_shown_stars(year: Year) -> Vec<(Day, [bool; 2])> {
+    let Ok(readme) = fs::read_to_string(README_PATH) else {
+        return vec![];
+    };
+
+    locate_table(&readme, STARS_MARKER)
+        .map(|x| parse_shown_stars(&readme[x.pos_start..x.pos_end], year))
+        .unwrap_or_default()
+}
+
+/// Update the table of earned stars of a year from its answer registry and stored timings.
+/// The table shows the year set in `AOC_YEAR`, so the readme is left untouched for other years.
+/// Returns whether the readme was changed.
+pub fn update_stars(year: Year) -> Result<bool, Error> {
+    if Year::from_env().is_some_and(|x| x != year) {
+        return Ok(false);
+    }
+
+    let current = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+    let mut readme = current.clone();
+    update_stars_content(
+        &mut readme,
+        &Answers::read_from_file(year).map_err(Error::Parser)?,
+        &Timings::read_from_file(year).map_err(Error::Parser)?,
+        year,
+    )?;
+
+    // NOTE: an unchanged readme is not written, so that its mtime does not trigger file watchers.
+    if readme == current {
+        return Ok(false);
+    }
+
+    fs::write(README_PATH, &readme)?;
+    Ok(true)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "memory" => Ok(Column::Memory),
+            "stddev" => Ok(Column::StdDev),
+            "share" => Ok(Column::Share),
+            "stars" => Ok(Column::Stars),
+            _ => Err(format!(
+                "unknown column `{s}`, expecting one of: parse, samples, memory, stddev, share, stars"
+            )),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortOrder::Day),
+            "slowest" => Ok(SortOrder::Slowest),
+            _ => Err(format!(
+                "unknown sort order `{s}`, expecting one of: day, slowest"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for ReadmeConfig {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut config = ReadmeConfig::default();
+
+        if let Some(columns) = json.get("columns") {
+            config.columns = columns
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.columns` to be an array.")?
+                .iter()
+                .map(|x| {
+                    x.get::<String>()
+                        .ok_or_else(|| "expected `json.columns` to contain strings.".to_string())
+                        .and_then(|x| Column::from_str(x))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(sort) = json.get("sort") {
+            config.sort = sort
+                .get::<String>()
+                .ok_or("expected `json.sort` to be a string.")?
+                .parse()?;
+        }
+
+        if let Some(heading_level) = json.get("heading_level") {
+            config.heading_level = heading_level
+                .get::<f64>()
+                .filter(|x| (1.0..=6.0).contains(*x) && x.fract() == 0.0)
+                .map(|x| {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let level = *x as u8;
+                    level
+                })
+                .ok_or("expected `json.heading_level` to be a number from 1 to 6.")?;
+        }
+
+        if let Some(path) = json.get("path") {
+            config.path = path
+                .get::<String>()
+                .ok_or("expected `json.path` to be a string.")?
+                .clone();
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{
+        earned_stars, get_path_for_bin, update_content, update_stars_content, Column, ReadmeConfig,
+        SortOrder, MARKER, STARS_MARKER,
+    };
+    use crate::{
+        day,
+        template::answers::Answers,
+        template::bench::{BenchStats, Statistic},
+        template::memory::MemoryStats,
+        template::timings::{PartTiming, Timing, Timings},
+        template::{all_days, PuzzleId},
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(PartTiming::from_nanos(10_000_000.0)),
+                    part_2: Some(PartTiming::from_nanos(20_000_000.0)),
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(PartTiming::from_nanos(30_000_000.0)),
+                    part_2: Some(PartTiming::from_nanos(40_000_000.0)),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(PartTiming::from_nanos(40_000_000.0)),
+                    part_2: Some(PartTiming::from_nanos(50_000_000.0)),
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -131,53 +668,554 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2024 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(s.matches(MARKER).count(), 0);
+        assert_eq!(s.matches("<!--- benchmarking table 2024 --->").count(), 2);
+        assert_eq!(s.matches("## 2024 Benchmarks").count(), 1);
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2024 --->",
+            "## 2024 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2024 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_statistic() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(BenchStats {
+            min: 1_000_000.0,
+            median: 2_000_000.0,
+            mean: 10_000_000.0,
+            p95: 3_000_000.0,
+            stddev: 0.0,
+            samples: 10,
+            outliers: 0,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            timings,
+            &[],
+            190.0,
+            Statistic::Median,
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2024 Benchmarks (median)"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2024-01.rs) | `2.0ms` | `20.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_timed_out_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].timed_out = Some(2);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            timings,
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(
+            s.contains("| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `timed out` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_step() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(PartTiming::from_nanos(5_000_000.0));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2024),
+            timings,
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2024-01.rs) | `-` | `10.0ms` | `20.0ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2024-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(BenchStats {
+            min: 1_000_000.0,
+            median: 2_000_000.0,
+            mean: 10_000_000.0,
+            p95: 3_000_000.0,
+            stddev: 500_000.0,
+            samples: 10,
+            outliers: 0,
+        });
+
+        let config = ReadmeConfig {
+            columns: vec![
+                Column::StdDev,
+                Column::Samples,
+                Column::Share,
+                Column::Stars,
+            ],
+            ..Default::default()
+        };
+        let stars = [(day!(1), [true, true]), (day!(2), [true, false])];
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &config,
+            year!(2024),
+            timings,
+            &stars,
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Samples | Share | Stars |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| :---: | :---: | :---: | :---: | :---: | :---:  |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/2024-01.rs) | `10.0ms ± 500.0µs` | `20.0ms` | 1 / 1 | 15.8% | ⭐⭐ |"
+            ),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` | 1 / 1 | 36.8% | ⭐ |"
+            ),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` | 1 / 1 | 47.4% |  |"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn format_share_without_total() {
+        let timings = Timings {
+            data: vec![Timing {
+                timed_out: Some(1),
+                ..Timing::new(day!(1))
+            }],
+            history: vec![],
+        };
+
+        let config = ReadmeConfig {
+            columns: vec![Column::Share],
+            ..Default::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &config,
+            year!(2024),
+            timings,
+            &[],
+            0.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(s.contains("NaN"), false);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2024-01.rs) | `timed out` | `-` | - |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+            peak_bytes: 1536,
+            total_bytes: 4096,
+            allocations: 3,
+        });
+
+        let config = ReadmeConfig {
+            columns: vec![Column::Memory],
+            ..Default::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &config,
+            year!(2024),
+            timings,
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(s.contains("| Day | Part 1 | Part 2 | Memory |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` | 1.5 KiB / - |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` | - / - |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_by_slowest() {
+        let config = ReadmeConfig {
+            sort: SortOrder::Slowest,
+            heading_level: 3,
+            ..Default::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &config,
+            year!(2024),
+            get_mock_timings(),
+            &[],
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        let rows: Vec<&str> = s.lines().filter(|x| x.starts_with("| [Day")).collect();
+        assert_eq!(s.contains("\n### 2024 Benchmarks\n"), true);
+        assert_eq!(rows[0].starts_with("| [Day 4]"), true);
+        assert_eq!(rows[2].starts_with("| [Day 1]"), true);
+    }
+
+    #[test]
+    fn adds_tables_of_other_years() {
+        let mut s = format!("foo\n{}{}\nbar", MARKER, MARKER);
+        for year in [year!(2024), year!(2023), year!(2024)] {
+            update_content(
+                &mut s,
+                &ReadmeConfig::default(),
+                year,
+                get_mock_timings(),
+                &[],
+                190.0,
+                Statistic::Mean,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            s.starts_with("foo\n<!--- benchmarking table 2024 --->\n"),
+            true
+        );
+        assert_eq!(s.ends_with("<!--- benchmarking table 2023 --->\nbar"), true);
+        assert_eq!(s.matches("## 2024 Benchmarks").count(), 1);
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert_eq!(s.contains("| [Day 1](./src/bin/2023-01.rs) |"), true);
+    }
+
+    #[test]
+    fn links_relative_to_target() {
+        let puzzle = |day| PuzzleId::new(year!(2024), day);
+        assert_eq!(
+            get_path_for_bin(puzzle(day!(1)), "README.md"),
+            "./src/bin/2024-01.rs"
+        );
+        assert_eq!(
+            get_path_for_bin(puzzle(day!(1)), "./README.md"),
+            "./src/bin/2024-01.rs"
+        );
+        assert_eq!(
+            get_path_for_bin(puzzle(day!(12)), "docs/BENCHMARKS.md"),
+            "../src/bin/2024-12.rs"
+        );
+    }
+
+    #[test]
+    fn parses_config() {
+        let json = r#"{ "columns": ["parse", "stars"], "sort": "slowest", "heading_level": 3, "path": "docs/BENCHMARKS.md" }"#;
+        let config = ReadmeConfig::try_from(json.to_string()).unwrap();
+        assert_eq!(config.columns, vec![Column::Parse, Column::Stars]);
+        assert_eq!(config.sort, SortOrder::Slowest);
+        assert_eq!(config.heading_level, 3);
+        assert_eq!(config.path, "docs/BENCHMARKS.md");
+
+        let config = ReadmeConfig::try_from("{}".to_string()).unwrap();
+        assert_eq!(config, ReadmeConfig::default());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for json in [
+            r#"{ "columns": ["speed"] }"#,
+            r#"{ "sort": "fastest" }"#,
+            r#"{ "heading_level": 7 }"#,
+            r#"{ "path": 1 }"#,
+        ] {
+            assert!(ReadmeConfig::try_from(json.to_string()).is_err());
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 1, "42");
+        answers.set(day!(3), 1, "7");
+        answers.set(day!(3), 2, "8");
+        answers
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n{}\nbar", STARS_MARKER, STARS_MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+
+        update_stars_content(&mut s, &get_mock_answers(), &timings, year!(2024)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ | ⭐ |",
+            "| [Day 4](https://adventofcode.com/2024/day/4) | ⭐ | ⭐ |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn replaces_stars_of_other_years() {
+        let mut s = [
+            STARS_MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 5](https://adventofcode.com/2023/day/5) | ⭐ |   |",
+            STARS_MARKER,
+        ]
+        .join("\n");
+
+        update_stars_content(
+            &mut s,
+            &get_mock_answers(),
+            &Timings::default(),
+            year!(2024),
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2024 Results"), true);
+        assert_eq!(
+            s.contains("| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |"),
+            true
+        );
+        assert_eq!(s.contains("Day 5"), false);
+    }
+
+    #[test]
+    fn awards_last_star_for_all_other_stars() {
+        let mut timings = Timings::default();
+        for day in all_days(year!(2024)) {
+            timings.data.push(Timing {
+                part_1: Some(PartTiming::from_nanos(1_000_000.0)),
+                part_2: (day != 25).then(|| PartTiming::from_nanos(1_000_000.0)),
+                ..Timing::new(day)
+            });
+        }
+
+        let stars = earned_stars(year!(2024), &[], &Answers::default(), &timings);
+        assert_eq!(stars.last(), Some(&(day!(25), [true, true])));
+
+        timings.data[0].part_2 = None;
+        let stars = earned_stars(year!(2024), &[], &Answers::default(), &timings);
+        assert_eq!(stars[0].0, day!(2));
+        assert_eq!(stars.last(), Some(&(day!(25), [true, false])));
+    }
+
+    #[test]
+    fn keeps_shown_stars() {
+        let mut s = [
+            STARS_MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 5](https://adventofcode.com/2024/day/5) | ⭐ |   |",
+            "| Day 6 | ⭐ | ⭐ |",
+            STARS_MARKER,
+        ]
+        .join("\n");
+
+        update_stars_content(
+            &mut s,
+            &get_mock_answers(),
+            &Timings::default(),
+            year!(2024),
+        )
+        .unwrap();
+        assert_eq!(
+            s.contains("| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 5](https://adventofcode.com/2024/day/5) | ⭐ |   |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 6](https://adventofcode.com/2024/day/6) | ⭐ | ⭐ |"),
+            true
+        );
+    }
+
+    #[test]
+    fn updates_stars_without_touching_benchmarks() {
+        let mut s = format!(
+            "{}{}\n{}\nfoo\n{}",
+            STARS_MARKER, STARS_MARKER, MARKER, MARKER
+        );
+        update_stars_content(
+            &mut s,
+            &get_mock_answers(),
+            &Timings::default(),
+            year!(2024),
+        )
+        .unwrap();
+        update_stars_content(
+            &mut s,
+            &get_mock_answers(),
+            &Timings::default(),
+            year!(2024),
+        )
+        .unwrap();
+        assert_eq!(s.matches(STARS_MARKER).count(), 2);
+        assert_eq!(s.ends_with(&format!("{}\nfoo\n{}", MARKER, MARKER)), true);
+    }
 }
@@ -0,0 +1,278 @@
+/// Machine-readable result of running a single solution part.
+/// Solution binaries print one report per line when invoked with `--json`.
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::bench::{stats_from_json, stats_to_json, BenchStats};
+use crate::template::memory::{memory_from_json, memory_to_json, MemoryStats};
+use crate::template::Day;
+
+/// The flag that switches solution binaries to JSON output.
+pub const JSON_FLAG: &str = "--json";
+
+/// The part number used to report the parse step of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// Name a part, or the parse step for [`PARSE_PART`].
+pub(crate) fn format_step(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// Represents the outcome and timing of a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    pub stats: Option<BenchStats>,
+    /// Only set for solutions built with the `memory` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartReport {
+    /// Serialize the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: serializing a map of strings and numbers can't fail.
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// Try to parse a line of child output as a report.
+    /// Returns `None` for lines that are not reports, e.g. debug output of a solution.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        PartReport::try_from(&json).ok()
+    }
+}
+
+/// A message from a running solution to the runner that collects its reports.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolutionEvent {
+    /// A part (or [`PARSE_PART`] for the parse step) started running.
+    Started(u8),
+    Report(PartReport),
+    /// Any other output of the solution, e.g. debug prints.
+    Output(String),
+    /// A line the solution wrote to stderr.
+    ErrorOutput(String),
+}
+
+impl SolutionEvent {
+    /// Interpret a line of child output.
+    pub fn from_line(line: String) -> Self {
+        if let Some(report) = PartReport::from_json_line(&line) {
+            return SolutionEvent::Report(report);
+        }
+
+        match started_from_json_line(&line) {
+            Some(part) => SolutionEvent::Started(part),
+            None => SolutionEvent::Output(line),
+        }
+    }
+}
+
+/// Serialize the start of a part to a single line of JSON, e.g. `{"started":1}`.
+pub fn started_to_json_line(part: u8) -> String {
+    let map = HashMap::from([("started".to_string(), JsonValue::Number(f64::from(part)))]);
+    // NOTE: serializing a map with a single number can't fail.
+    JsonValue::Object(map).stringify().unwrap()
+}
+
+/// Try to parse a line of child output as the start of a part.
+fn started_from_json_line(line: &str) -> Option<u8> {
+    let json = JsonValue::from_str(line.trim()).ok()?;
+    let json = json.get::<HashMap<String, JsonValue>>()?;
+
+    if json.len() != 1 {
+        return None;
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    json.get("started")?.get::<f64>().map(|x| *x as u8)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+        map.insert("memory".into(), memory_to_json(value.memory.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let stats = stats_from_json(json, "stats")?;
+        let memory = memory_from_json(json, "memory")?;
+
+        Ok(PartReport {
+            day,
+            part,
+            answer: answer.cloned(),
+            duration: Duration::from_nanos(nanos),
+            samples,
+            stats,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::PartReport;
+    use crate::{
+        day,
+        template::{bench::BenchStats, memory::MemoryStats},
+    };
+
+    fn get_mock_report(answer: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part: 2,
+            answer: answer.map(String::from),
+            duration: Duration::from_nanos(74_130),
+            samples: 100_000,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = get_mock_report(Some("42"));
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let mut report = get_mock_report(Some("42"));
+        report.stats = BenchStats::from_samples(&[
+            Duration::from_nanos(10),
+            Duration::from_nanos(12),
+            Duration::from_nanos(14),
+        ]);
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn roundtrips_memory() {
+        let mut report = get_mock_report(Some("42"));
+        report.memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 8192,
+            allocations: 3,
+        });
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn roundtrips_answers_with_patterns() {
+        let report = get_mock_report(Some("@ @ @ ( ) ms (2s @ 5 samples)\n#..#\n\"x\""));
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn roundtrips_missing_answers() {
+        let report = get_mock_report(None);
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert_eq!(parsed.answer, None);
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartReport::from_json_line(""), None);
+        assert_eq!(PartReport::from_json_line("Part 1: 0 (74.13ns)"), None);
+        assert_eq!(PartReport::from_json_line("{ \"day\": \"01\" }"), None);
+        assert_eq!(PartReport::from_json_line("[1, 2, 3]"), None);
+    }
+
+    #[test]
+    fn parses_started_parts() {
+        use super::{started_to_json_line, SolutionEvent};
+
+        assert_eq!(
+            SolutionEvent::from_line(started_to_json_line(0)),
+            SolutionEvent::Started(0)
+        );
+        assert_eq!(
+            SolutionEvent::from_line("{\"started\": 2, \"x\": 1}".into()),
+            SolutionEvent::Output("{\"started\": 2, \"x\": 1}".into())
+        );
+    }
+}
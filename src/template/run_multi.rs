@@ -1,41 +1,179 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread,
+    time::Instant,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    BenchConfig, Day, PuzzleId, TimeoutConfig, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    answers::Answers,
+    in_process,
+    report::{format_step, PartReport, SolutionEvent, PARSE_PART},
+    runner::print_report,
+    timings::{PartTiming, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// The outcome of running the solutions of a set of days.
+pub struct MultiRun {
+    /// Only set when solutions were benched.
+    pub timings: Option<Timings>,
+    /// Parts whose answer differs from the confirmed answer in the registry.
+    pub mismatches: Vec<(Day, u8)>,
+    /// Days whose solution failed to compile.
+    pub build_failures: Vec<Day>,
+}
 
-    let mut need_space = false;
+/// Run the solutions of a set of days of `year`. When `bench_config` is set, solutions are benched and their timings are returned.
+/// Parts that exceed their time limit in `timeouts` are killed and reported as timed out.
+/// Answers are checked against the answer registry.
+///
+/// Unless benching, up to `jobs` days run concurrently. Their output is buffered and printed in day order.
+/// When `part` is set, only that part of each day is run.
+///
+/// Solutions run in the current process if they were registered via [`in_process::register`],
+/// otherwise each day is run as a separate binary.
+/// When `memory` is set, the heap usage of each part is measured, which requires separate binaries.
+#[allow(clippy::too_many_arguments)]
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench_config: Option<&BenchConfig>,
+    timeouts: &TimeoutConfig,
+    jobs: usize,
+    part: Option<u8>,
+    memory: bool,
+) -> MultiRun {
+    // NOTE: without a readable registry, mismatched answers would go unnoticed.
+    let answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read answer registry: {e}");
+        process::exit(1);
+    });
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let run_day = |index: usize, printer: &mut Printer| {
+        let day = days[index];
+        let puzzle = PuzzleId::new(year, day);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if index > 0 {
+            printer.out(String::new());
+        }
+        printer.out(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+        printer.out("------".into());
+
+        let output = if in_process::is_enabled() && !memory {
+            in_process_commands::run_solution(puzzle, bench_config, timeouts, part, printer)
+        } else {
+            child_commands::run_solution(
+                puzzle,
+                bench_config,
+                is_release,
+                timeouts,
+                part,
+                memory,
+                printer,
+            )
+            .unwrap()
+        };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        let mut mismatches: Vec<u8> = vec![];
+
+        for report in output.reports.iter().filter(|r| r.part != PARSE_PART) {
+            if let Some(expected) =
+                answers.find_mismatch(day, report.part, report.answer.as_deref())
+            {
+                printer.out(format!(
+                    "{ANSI_RED}Part {}: answer does not match confirmed answer `{expected}`.{ANSI_RESET}",
+                    report.part
+                ));
+                mismatches.push(report.part);
             }
-        });
+        }
+
+        if output.build_failed {
+            printer.out(format!("{ANSI_RED}Build failed.{ANSI_RESET}"));
+        } else if output.reports.is_empty() && output.timed_out.is_none() {
+            printer.out("Not solved.".into());
+        }
+
+        (output, mismatches)
+    };
+
+    let results: Vec<(SolutionOutput, Vec<u8>)> = if bench_config.is_some() || jobs <= 1 {
+        // NOTE: benchmarks run one by one, so that they don't compete for resources.
+        (0..days.len())
+            .map(|index| run_day(index, &mut Printer::streaming()))
+            .collect()
+    } else {
+        run_concurrently(days.len(), jobs, &run_day)
+    };
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut timed_out: Vec<(Day, u8)> = vec![];
+    let mut mismatches: Vec<(Day, u8)> = vec![];
+    let mut build_failures: Vec<Day> = vec![];
+
+    for (day, (output, mismatched_parts)) in days.iter().zip(results) {
+        if let Some(part) = output.timed_out {
+            timed_out.push((*day, part));
+        }
+
+        if output.build_failed {
+            build_failures.push(*day);
+        }
+
+        mismatches.extend(mismatched_parts.into_iter().map(|part| (*day, part)));
 
-    if is_timed {
-        let timings = Timings { data: timings };
+        if !output.reports.is_empty() || output.timed_out.is_some() {
+            let mut timing = collect_timing(*day, &output.reports);
+            timing.timed_out = output.timed_out;
+            timings.push(timing);
+        }
+    }
+
+    if !timed_out.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}Timed out:{ANSI_RESET} {}",
+            format_parts(&timed_out)
+        );
+    }
+
+    if !build_failures.is_empty() {
+        let days: Vec<String> = build_failures
+            .iter()
+            .map(|day| format!("Day {day}"))
+            .collect();
+        println!(
+            "\n{ANSI_RED}{ANSI_BOLD}Build failed:{ANSI_RESET}{ANSI_RED} {}{ANSI_RESET}",
+            days.join(", ")
+        );
+    }
+
+    if !mismatches.is_empty() {
+        println!(
+            "\n{ANSI_RED}{ANSI_BOLD}Wrong answers:{ANSI_RESET}{ANSI_RED} {}{ANSI_RESET}",
+            format_parts(&mismatches)
+        );
+    }
+
+    let timings = if bench_config.is_some() {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -43,6 +181,214 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        mismatches,
+        build_failures,
+    }
+}
+
+/// Run `count` tasks on up to `jobs` threads. The output of each task is buffered and printed in order,
+/// as soon as all previous tasks have finished.
+fn run_concurrently<T: Send>(
+    count: usize,
+    jobs: usize,
+    task: &(impl Fn(usize, &mut Printer) -> T + Sync),
+) -> Vec<T> {
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<(usize, Printer, T)>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(count) {
+            let tx = tx.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                if index >= count {
+                    break;
+                }
+
+                let mut printer = Printer::buffered();
+                let result = task(index, &mut printer);
+                if tx.send((index, printer, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut finished: BTreeMap<usize, (Printer, T)> = BTreeMap::new();
+        let mut results: Vec<T> = Vec::with_capacity(count);
+
+        for (index, printer, result) in rx {
+            finished.insert(index, (printer, result));
+
+            while let Some((printer, result)) = finished.remove(&results.len()) {
+                printer.flush();
+                results.push(result);
+            }
+        }
+
+        results
+    })
+}
+
+/// Output of a single day. Printed right away when days run one by one, buffered when they run concurrently.
+pub struct Printer {
+    buffer: Option<Vec<SolutionEvent>>,
+}
+
+impl Printer {
+    pub fn streaming() -> Self {
+        Printer { buffer: None }
+    }
+
+    fn buffered() -> Self {
+        Printer {
+            buffer: Some(vec![]),
+        }
+    }
+
+    /// Print a line to stdout.
+    pub fn out(&mut self, line: String) {
+        self.event(SolutionEvent::Output(line));
+    }
+
+    /// Print a line to stderr.
+    pub fn err(&mut self, line: String) {
+        self.event(SolutionEvent::ErrorOutput(line));
+    }
+
+    /// Print an event of a solution.
+    pub fn event(&mut self, event: SolutionEvent) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.push(event),
+            None => print_event(&event),
+        }
+    }
+
+    /// Print all buffered output.
+    fn flush(self) {
+        self.buffer.iter().flatten().for_each(print_event);
+    }
+}
+
+fn print_event(event: &SolutionEvent) {
+    match event {
+        SolutionEvent::Started(_) => {}
+        SolutionEvent::Report(report) => print_report(report),
+        SolutionEvent::Output(line) => println!("{line}"),
+        SolutionEvent::ErrorOutput(line) => eprintln!("{line}"),
+    }
+}
+
+fn format_parts(parts: &[(Day, u8)]) -> String {
+    parts
+        .iter()
+        .map(|(day, part)| format!("Day {day} ({})", format_step(*part)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Build the timing of a day from the reports of its parts.
+pub fn collect_timing(day: Day, reports: &[PartReport]) -> Timing {
+    let mut timing = Timing::new(day);
+
+    // NOTE: the parse step has no answer, but should be timed nonetheless.
+    for report in reports
+        .iter()
+        .filter(|r| r.answer.is_some() || r.part == PARSE_PART)
+    {
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        let part_timing = PartTiming {
+            nanos: report.duration.as_nanos() as f64,
+            samples: report.samples as u64,
+            stats: report.stats.clone(),
+            memory: report.memory,
+        };
+
+        match report.part {
+            PARSE_PART => timing.parse = Some(part_timing),
+            1 => timing.part_1 = Some(part_timing),
+            2 => timing.part_2 = Some(part_timing),
+            _ => continue,
+        }
+    }
+
+    timing
+}
+
+/// The reports a solution produced before it finished or was stopped.
+#[derive(Default)]
+pub struct SolutionOutput {
+    pub reports: Vec<PartReport>,
+    /// The part that was running when the time limit was hit.
+    pub timed_out: Option<u8>,
+    /// Whether the solution failed to compile, its diagnostics are printed to stderr.
+    pub build_failed: bool,
+}
+
+/// Print and collect the events of a running solution until it finishes or a part exceeds its time limit.
+fn receive_reports(
+    day: Day,
+    timeouts: &TimeoutConfig,
+    rx: &Receiver<SolutionEvent>,
+    printer: &mut Printer,
+) -> SolutionOutput {
+    let mut reports: Vec<PartReport> = vec![];
+    let mut timed_out = None;
+
+    // NOTE: each step announces its start, so neither the startup of the solution nor its parse step
+    // count against the limit of part 1.
+    let mut part: Option<u8> = None;
+    let mut part_started = Instant::now();
+
+    loop {
+        let timeout = part.and_then(|part| Some((part, timeouts.for_part(day, part)?)));
+
+        let event = match timeout {
+            Some((part, timeout)) => {
+                match rx.recv_timeout(timeout.saturating_sub(part_started.elapsed())) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => {
+                        printer.out(format!(
+                            "{}: ✖ {ANSI_BOLD}timed out{ANSI_RESET} after {timeout:.1?}",
+                            format_step(part)
+                        ));
+                        timed_out = Some(part);
+                        break;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            None => match rx.recv() {
+                Ok(event) => event,
+                Err(_) => break,
+            },
+        };
+
+        match &event {
+            SolutionEvent::Started(started) => {
+                part = Some(*started);
+                part_started = Instant::now();
+            }
+            SolutionEvent::Report(report) => {
+                reports.push(report.clone());
+            }
+            _ => {}
+        }
+
+        printer.event(event);
+    }
+
+    SolutionOutput {
+        reports,
+        timed_out,
+        build_failed: false,
     }
 }
 
@@ -59,47 +405,97 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{receive_reports, Error, Printer, SolutionOutput};
+    use crate::template::{
+        report::{SolutionEvent, JSON_FLAG},
+        BenchConfig, PuzzleId, TimeoutConfig,
+    };
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::PathBuf,
         process::{Command, Stdio},
+        str::FromStr,
+        sync::mpsc,
         thread,
     };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    use tinyjson::JsonValue;
+
+    /// Run the solution bin for a given puzzle
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        bench_config: Option<&BenchConfig>,
+        is_release: bool,
+        timeouts: &TimeoutConfig,
+        part: Option<u8>,
+        memory: bool,
+        printer: &mut Printer,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !puzzle.bin_path().exists() {
+            return Ok(SolutionOutput::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut build_args: Vec<String> =
+            vec!["--quiet".into(), "--bin".into(), puzzle.to_string()];
 
         if is_release {
-            args.push("--release");
+            build_args.push("--release".into());
+        }
+
+        if memory {
+            // install the counting allocator in the solution binary.
+            build_args.push("--features".into());
+            build_args.push("memory".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        // build ahead of running, so compilation does not count against time limits.
+        // NOTE: diagnostics are still rendered to stderr, stdout lists the built artifacts.
+        let build = Command::new("cargo")
+            .arg("build")
+            .args(&build_args)
+            .arg("--message-format=json-render-diagnostics")
+            .output()?;
+        String::from_utf8_lossy(&build.stderr)
+            .lines()
+            .for_each(|line| printer.err(line.into()));
+
+        if !build.status.success() {
+            return Ok(SolutionOutput {
+                build_failed: true,
+                ..SolutionOutput::default()
+            });
+        }
+
+        // NOTE: run the binary directly, so that a timed out solution can be killed.
+        // killing `cargo run` would only stop cargo and leave the solution running on some platforms.
+        let Some(executable) =
+            find_executable(&String::from_utf8_lossy(&build.stdout), &puzzle.to_string())
+        else {
+            return Ok(SolutionOutput::default());
+        };
+
+        // request machine-readable reports from the child.
+        let mut args: Vec<String> = vec![JSON_FLAG.into()];
+
+        if let Some(bench_config) = bench_config {
+            // mirror `--time` flag and benchmark config to child invocations.
+            args.push("--time".into());
+            args.extend(bench_config.to_args());
+        }
+
+        if let Some(part) = part {
+            args.push("--part".into());
+            args.push(part.to_string());
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while collecting reports.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,150 +504,252 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        // read output on separate threads, so the watchdog can give up waiting for it.
+        let (tx, rx) = mpsc::channel();
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stderr_tx = tx.clone();
+        let stderr_thread = thread::spawn(move || {
+            for line in stderr.lines().map_while(Result::ok) {
+                if stderr_tx.send(SolutionEvent::ErrorOutput(line)).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stdout_thread = thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if tx.send(SolutionEvent::from_line(line)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let output = receive_reports(puzzle.day, timeouts, &rx, printer);
+
+        if output.timed_out.is_some() {
+            cmd.kill()?;
         }
 
-        thread.join().unwrap();
+        stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
         cmd.wait()?;
 
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Find the path of the executable of binary `name` in the JSON messages of `cargo build`.
+    pub(super) fn find_executable(messages: &str, name: &str) -> Option<PathBuf> {
+        messages.lines().find_map(|line| {
+            let json = JsonValue::from_str(line).ok()?;
+            let json = json.get::<HashMap<String, JsonValue>>()?;
+
+            let is_bin = json
+                .get("target")
+                .and_then(|x| x.get::<HashMap<String, JsonValue>>())
+                .and_then(|x| x.get("name"))
+                .and_then(|x| x.get::<String>())
+                .is_some_and(|x| x == name);
+
+            if !is_bin {
+                return None;
+            }
+
+            json.get("executable")
+                .and_then(|x| x.get::<String>())
+                .map(PathBuf::from)
+        })
+    }
+}
+
+/// Solutions registered via [`in_process::register`] are run on a separate thread of the current process.
+pub mod in_process_commands {
+    use super::{receive_reports, Printer, SolutionOutput};
+    use crate::template::{in_process, runner::RunContext, BenchConfig, PuzzleId, TimeoutConfig};
+    use std::{fs, sync::mpsc, thread};
+
+    /// Run the registered solution for a given puzzle.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        bench_config: Option<&BenchConfig>,
+        timeouts: &TimeoutConfig,
+        part: Option<u8>,
+        printer: &mut Printer,
+    ) -> SolutionOutput {
+        let Some(solution) = in_process::get(puzzle) else {
+            return SolutionOutput::default();
         };
 
+        let input_path = puzzle.input_path();
+        let input = match fs::read_to_string(&input_path) {
+            Ok(input) => input,
+            Err(e) => {
+                printer.err(format!(
+                    "Could not open input file \"{}\": {e}",
+                    input_path.display()
+                ));
+                return SolutionOutput::default();
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let bench_config = bench_config.cloned();
+
+        let handle = thread::spawn(move || {
+            solution(
+                &input,
+                &RunContext::in_process(puzzle, bench_config, part, tx),
+            );
+        });
+
+        let output = receive_reports(puzzle.day, timeouts, &rx, printer);
+
+        if output.timed_out.is_some() {
+            // NOTE: threads can't be killed, the timed out part keeps running until the runner exits.
+            printer.err(
+                "Note: the timed out part keeps running in the background and may slow down later days."
+                    .into(),
+            );
+        } else {
+            // NOTE: a panic has already been printed by the panic hook, like the stderr of a crashed child.
+            let _ = handle.join();
+        }
+
         output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    }
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::collect_timing;
+    use crate::{day, template::report::PartReport};
+
+    fn get_mock_report(part: u8, answer: Option<&str>, nanos: u64) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            answer: answer.map(String::from),
+            duration: Duration::from_nanos(nanos),
+            samples: 100,
+            stats: None,
+            memory: None,
+        }
+    }
 
-                timings.total_nanos += nanos;
-            });
+    #[test]
+    fn collects_timings() {
+        let res = collect_timing(
+            day!(1),
+            &[
+                get_mock_report(1, Some("0"), 74),
+                get_mock_report(2, Some("10"), 74_130_000),
+            ],
+        );
+        assert_eq!(res.total_nanos(), 74_130_074_f64);
+        assert_eq!(res.part_1.as_ref().unwrap().nanos, 74_f64);
+        assert_eq!(res.part_1.unwrap().samples, 100);
+        assert_eq!(res.part_2.unwrap().nanos, 74_130_000_f64);
+    }
 
-        timings
+    #[test]
+    fn collects_missing_parts() {
+        let res = collect_timing(
+            day!(1),
+            &[
+                get_mock_report(1, None, 74),
+                get_mock_report(2, None, 74_130_000),
+            ],
+        );
+        assert_eq!(res.total_nanos(), 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    #[test]
+    fn collects_parse_step() {
+        let res = collect_timing(
+            day!(1),
+            &[
+                get_mock_report(0, None, 1_000),
+                get_mock_report(1, Some("1"), 2_000),
+                get_mock_report(2, Some("2"), 3_000),
+            ],
+        );
+        assert_eq!(res.total_nanos(), 6_000_f64);
+        assert_eq!(res.parse.unwrap().nanos, 1_000_f64);
+        assert_eq!(res.part_1.unwrap().nanos, 2_000_f64);
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn collects_single_parts() {
+        let res = collect_timing(day!(1), &[get_mock_report(2, Some("1"), 2_000_000_000)]);
+        assert_eq!(res.total_nanos(), 2_000_000_000_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.unwrap().nanos, 2_000_000_000_f64);
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn times_parse_step_separately() {
+        use super::{receive_reports, Printer};
+        use crate::template::{report::SolutionEvent, TimeoutConfig};
+        use std::{sync::mpsc, thread};
+
+        let timeouts = TimeoutConfig {
+            rules: vec!["01:1=100ms".parse().unwrap()],
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let solution = thread::spawn(move || {
+            tx.send(SolutionEvent::Started(0)).unwrap();
+            thread::sleep(Duration::from_millis(200));
+            tx.send(SolutionEvent::Report(get_mock_report(0, None, 1)))
+                .unwrap();
+            tx.send(SolutionEvent::Started(1)).unwrap();
+            tx.send(SolutionEvent::Report(get_mock_report(1, Some("1"), 1)))
+                .unwrap();
+        });
+
+        let output = receive_reports(day!(1), &timeouts, &rx, &mut Printer::buffered());
+        solution.join().unwrap();
+
+        assert_eq!(output.timed_out, None);
+        assert_eq!(output.reports.len(), 2);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn times_out_parts() {
+        use super::{receive_reports, Printer};
+        use crate::template::{report::SolutionEvent, TimeoutConfig};
+        use std::sync::mpsc;
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+        let timeouts = TimeoutConfig {
+            rules: vec!["01=10ms".parse().unwrap()],
+        };
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let (tx, rx) = mpsc::channel();
+        tx.send(SolutionEvent::Started(0)).unwrap();
+
+        let output = receive_reports(day!(1), &timeouts, &rx, &mut Printer::buffered());
+        assert_eq!(output.timed_out, Some(0));
+    }
+
+    #[test]
+    fn finds_executables_in_build_output() {
+        use super::child_commands::find_executable;
+        use std::path::PathBuf;
+
+        let messages = [
+            r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#,
+            r#"{"reason":"compiler-artifact","target":{"name":"2024-01","kind":["bin"]},"executable":"/repo/target/debug/2024-01"}"#,
+            r#"{"reason":"build-finished","success":true}"#,
+        ]
+        .join("\n");
+
+        assert_eq!(
+            find_executable(&messages, "2024-01"),
+            Some(PathBuf::from("/repo/target/debug/2024-01"))
+        );
+        assert_eq!(find_executable(&messages, "2024-02"), None);
     }
 }
@@ -1,169 +1,457 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
-use std::hint::black_box;
+use std::fs;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::mpsc::Sender;
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::SubmissionVerdict;
+use crate::template::bench::{bench, format_nanos, BenchConfig, BenchStats};
+use crate::template::memory::{self, format_bytes, MemoryStats};
+use crate::template::report::{
+    started_to_json_line, PartReport, SolutionEvent, JSON_FLAG, PARSE_PART,
+};
+use crate::template::selection::parse_part;
+use crate::template::submissions::{format_age, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, read_file, readme_benchmarks, PuzzleId, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
+
+/// The flag that makes solution binaries read a different input file, e.g. an example.
+pub const INPUT_FLAG: &str = "--input";
+
+/// Read the input of a solution binary: the file passed via [`INPUT_FLAG`], or the input of `puzzle`.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|x| x == INPUT_FLAG) {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --input <path>");
+                process::exit(1);
+            };
+            fs::read_to_string(path).expect("could not open input file")
+        }
+        None => read_file("inputs", puzzle),
+    }
+}
+
+/// Where the reports of a solution go.
+enum ReportSink {
+    /// Pretty-print reports to the terminal.
+    Terminal,
+    /// Print one JSON report per line, see [`JSON_FLAG`].
+    Json,
+    /// Send reports to a runner in the same process.
+    Channel(Sender<SolutionEvent>),
+}
+
+/// The configuration of a single solution run, shared by all of its parts.
+pub struct RunContext {
+    puzzle: PuzzleId,
+    sink: ReportSink,
+    bench_config: Option<BenchConfig>,
+    submit: Option<u8>,
+    /// Sleep and submit again when a submission is rate limited.
+    wait: bool,
+    /// Only run this part, if set.
+    part: Option<u8>,
+}
+
+impl RunContext {
+    /// Configure a run from the command-line arguments of a solution binary.
+    pub fn from_env(puzzle: PuzzleId) -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let sink = if args.iter().any(|x| x == JSON_FLAG) {
+            ReportSink::Json
+        } else {
+            ReportSink::Terminal
+        };
+
+        let bench_config = args.iter().any(|x| x == "--time").then(|| {
+            BenchConfig::from_env().unwrap_or_else(|e| {
+                eprintln!("Invalid benchmark arguments: {e}");
+                process::exit(1);
+            })
+        });
+
+        let submit = args.iter().position(|x| x == "--submit").map(|index| {
+            let Some(Ok(part)) = args.get(index + 1).map(|x| x.parse::<u8>()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        let part = args.iter().position(|x| x == "--part").map(|index| {
+            let Some(Ok(part)) = args.get(index + 1).map(|x| parse_part(x)) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        RunContext {
+            puzzle,
+            sink,
+            bench_config,
+            submit,
+            wait: args.iter().any(|x| x == "--wait"),
+            part,
+        }
+    }
+
+    /// Configure a run inside the runner process. Reports are sent to `sender` instead of being printed.
+    pub fn in_process(
+        puzzle: PuzzleId,
+        bench_config: Option<BenchConfig>,
+        part: Option<u8>,
+        sender: Sender<SolutionEvent>,
+    ) -> Self {
+        RunContext {
+            puzzle,
+            sink: ReportSink::Channel(sender),
+            bench_config,
+            submit: None,
+            wait: false,
+            part,
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self.sink, ReportSink::Terminal)
+    }
+
+    /// Announce that a part (or [`PARSE_PART`]) starts running, so the runner can apply its time limit.
+    fn emit_started(&self, part: u8) {
+        match &self.sink {
+            ReportSink::Terminal => {}
+            ReportSink::Json => println!("{}", started_to_json_line(part)),
+            ReportSink::Channel(sender) => {
+                let _ = sender.send(SolutionEvent::Started(part));
+            }
+        }
+    }
+
+    fn emit(&self, report: PartReport) {
+        match &self.sink {
+            ReportSink::Terminal => print_report(&report),
+            ReportSink::Json => println!("{}", report.to_json_line()),
+            // NOTE: the runner stops listening when a part times out, so send errors are expected.
+            ReportSink::Channel(sender) => {
+                let _ = sender.send(SolutionEvent::Report(report));
+            }
+        }
+    }
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    ctx: &RunContext,
+    part: u8,
+) {
+    if ctx.part.is_some_and(|x| x != part) {
+        return;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    ctx.emit_started(part);
+    let (result, measurement) = run_timed(func, input, ctx, |result| {
+        print_intermediate_result(result, &part_str);
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    ctx.emit(PartReport {
+        day: ctx.puzzle.day,
+        part,
+        answer: result.as_ref().map(ToString::to_string),
+        duration: measurement.duration,
+        samples: measurement.samples,
+        stats: measurement.stats,
+        memory: measurement.memory,
+    });
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        if ctx.submit == Some(part) {
+            submit_result(result, ctx.puzzle, part, ctx.wait);
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the parse step of a solution and return its output, which is shared by both parts.
+/// The parse step is timed like a part and reported as part `0`.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, ctx: &RunContext) -> T {
+    ctx.emit_started(PARSE_PART);
+    let (result, measurement) = run_timed(func, input, ctx, |_| {
+        print!("Parse:");
+    });
+
+    ctx.emit(PartReport {
+        day: ctx.puzzle.day,
+        part: PARSE_PART,
+        answer: None,
+        duration: measurement.duration,
+        samples: measurement.samples,
+        stats: measurement.stats,
+        memory: measurement.memory,
+    });
+
+    result
+}
+
+/// Timing and heap usage of a part.
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    stats: Option<BenchStats>,
+    /// Heap usage of the first execution.
+    memory: Option<MemoryStats>,
+}
+
+/// Run a solution part. The behavior differs depending on whether the run is benched:
+///  1. without a [`BenchConfig`], the function is executed once.
+///  2. with it, the function is benched according to the config
+///     (by default, approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// `hook` is called with the result of the first execution when printing to the terminal.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    ctx: &RunContext,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        memory::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    if ctx.is_terminal() {
+        hook(&result);
+    }
+
+    if let Some(config) = &ctx.bench_config {
+        if ctx.is_terminal() {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        let stats = bench(func, input, &base_time, config);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(stats.mean.round() as u64);
+        // NOTE: the mean excludes outliers, so the reported samples do too. Outliers are listed with the stats.
+        let samples = u128::from(stats.samples);
+
+        let measurement = Measurement {
+            duration,
+            samples,
+            stats: Some(stats),
+            memory,
+        };
+        (result, measurement)
     } else {
-        (base_time, 1)
-    };
+        let measurement = Measurement {
+            duration: base_time,
+            samples: 1,
+            stats: None,
+            memory,
+        };
+        (result, measurement)
+    }
+}
 
-    (result, run.0, run.1)
+fn format_duration(duration: &Duration, samples: u128) -> String {
+    if samples == 1 {
+        format!(" ({duration:.1?})")
+    } else {
+        format!(" ({duration:.1?} @ {samples} samples)")
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Pretty-print a finished part to the terminal, replacing its intermediate result.
+pub fn print_report(report: &PartReport) {
+    print!("\r");
+    println!("{}", format_report(report));
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Format a finished part for the terminal. Multi-line answers and benchmark statistics span multiple lines.
+pub fn format_report(report: &PartReport) -> String {
+    let duration_str = format_duration(&report.duration, report.samples);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let mut str = if report.part == PARSE_PART {
+        format!("Parse:{duration_str}")
+    } else {
+        let part = format!("Part {}", report.part);
+        match &report.answer {
+            Some(answer) if answer.contains('\n') => format!("{part}: ▼ {duration_str}\n{answer}"),
+            Some(answer) => format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}"),
+            None => format!("{part}: ✖             "),
+        }
+    };
 
-    let mut timers: Vec<Duration> = vec![];
+    if let Some(stats) = &report.stats {
+        str.push('\n');
+        str.push_str(&format_stats(stats));
+    }
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+    if let Some(memory) = &report.memory {
+        str.push('\n');
+        str.push_str(&format_memory(memory));
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    str
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_memory(memory: &MemoryStats) -> String {
+    format!(
+        "        {ANSI_ITALIC}peak {} · total {} · {} allocations{ANSI_RESET}",
+        format_bytes(memory.peak_bytes),
+        format_bytes(memory.total_bytes),
+        memory.allocations
+    )
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_stats(stats: &BenchStats) -> String {
+    let mut str = format!(
+        "        {ANSI_ITALIC}min {} · median {} · p95 {} · σ {}",
+        format_nanos(stats.min),
+        format_nanos(stats.median),
+        format_nanos(stats.p95),
+        format_nanos(stats.stddev),
+    );
+
+    if stats.outliers > 0 {
+        str.push_str(&format!(" · {} outliers", stats.outliers));
     }
-}
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+    str.push_str(ANSI_RESET);
+    str
+}
 
+/// Print the result of the first execution of a part, before it is benched.
+fn print_intermediate_result<T: Display>(result: &Option<T>, part: &str) {
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+        Some(result) if result.to_string().contains('\n') => print!("{part}: ▼ "),
+        Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => print!("{part}: ✖"),
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
+/// How long to wait after a rate limited submission if the server did not say.
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///
+/// Answers that were rejected before are not submitted again. Every judged submission is recorded in the submission log,
+/// correct answers are additionally stored in the answer registry. If `wait` is set, rate limited submissions are retried
+/// once the rate limit has passed.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    wait: bool,
+) -> Option<Result<SubmissionVerdict, aoc_cli::AocClientError>> {
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
+        process::exit(1);
+    }
 
-    if !args.contains(&"--submit".into()) {
+    let answer = result.to_string();
+    let PuzzleId { year, day } = puzzle;
+    // NOTE: without a readable log, answers that were rejected before could be submitted again.
+    let mut submissions = match Submissions::read_from_file(year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Failed to read submission log, not submitting: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(previous) = submissions.find_rejected(day, part, &answer) {
+        eprintln!(
+            "{ANSI_RED}Answer `{answer}` was already rejected ({}, {}). Not submitting it again.{ANSI_RESET}",
+            previous.verdict,
+            format_age(previous.timestamp)
+        );
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if let Some(previous) = submissions.find_contradicting_bound(day, part, &answer) {
+        eprintln!(
+            "{ANSI_RED}Warning: `{}` was rejected as {} ({}), so `{answer}` is likely wrong as well.{ANSI_RESET}",
+            previous.answer,
+            previous.verdict,
+            format_age(previous.timestamp)
+        );
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let verdict = loop {
+        println!("Submitting result...");
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        let verdict = match aoc_cli::submit(puzzle, part, &answer) {
+            Ok(verdict) => verdict,
+            Err(e) => {
+                eprintln!("{ANSI_RED}Failed to submit answer: {e}{ANSI_RESET}");
+                return Some(Err(e));
+            }
+        };
+
+        let SubmissionVerdict::RateLimited(time_left) = verdict else {
+            break verdict;
+        };
+
+        let time_left = time_left.unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+        eprintln!(
+            "{ANSI_RED}Submission was rate limited, {}s left to wait.{ANSI_RESET}",
+            time_left.as_secs()
+        );
+
+        if !wait {
+            eprintln!(
+                "Append `--wait` to submit again automatically once the rate limit has passed."
+            );
+            return Some(Ok(verdict));
+        }
+
+        // NOTE: the reported time is rounded, wait a little longer to not be rate limited again.
+        println!("Waiting to submit again...");
+        thread::sleep(time_left + Duration::from_secs(1));
     };
 
-    if part_submit != part {
-        return None;
+    submissions.record(day, part, &answer, verdict);
+    if let Err(e) = submissions.store_file(year) {
+        eprintln!("Failed to store submission log: {e}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if verdict == SubmissionVerdict::Correct {
+        match Answers::read_from_file(year) {
+            Ok(mut answers) => {
+                answers.set(day, part, &answer);
+                match answers.store_file(year) {
+                    Ok(()) => println!("Stored answer in the answer registry."),
+                    Err(e) => eprintln!("Failed to store answer in the answer registry: {e}"),
+                }
+            }
+            Err(e) => eprintln!("Failed to read answer registry, answer was not stored: {e}"),
+        }
+
+        if readme_benchmarks::update_stars(year).is_ok_and(|x| x) {
+            println!("Updated stars in the readme.");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(Ok(verdict))
 }
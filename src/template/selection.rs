@@ -0,0 +1,230 @@
+/// Selection of the days and parts that `all` and `time` run.
+use std::{collections::HashSet, error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::bench::parse_duration;
+use crate::template::timings::Timings;
+use crate::template::{all_days, AllDays, Day, DayFromStrError, Year};
+
+/// A set of days, parsed from values like `6`, `1..=10`, `1..11` or `3,7,12`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DaySet(pub HashSet<Day>);
+
+/// Days and parts selected via command-line arguments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DaySelection {
+    /// Explicitly selected days. All days if not set.
+    pub days: Option<DaySet>,
+    /// Only run days that don't have stored timings for both parts.
+    pub only_incomplete: bool,
+    /// Only run days whose stored total time (or the time of the selected part) exceeds this duration.
+    pub slower_than: Option<Duration>,
+    /// Only run a single part of each day.
+    pub part: Option<u8>,
+}
+
+impl DaySelection {
+    /// Read a selection from command-line arguments.
+    /// Expects the day selector to be the remaining free argument, so this must be called after all other options are read.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let only_incomplete = args.contains("--only-incomplete");
+        let slower_than = args.opt_value_from_fn("--slower-than", parse_duration)?;
+        let part = args.opt_value_from_fn("--part", parse_part)?;
+
+        Ok(DaySelection {
+            days: args.opt_free_from_str()?,
+            only_incomplete,
+            slower_than,
+            part,
+        })
+    }
+
+    /// Resolve the selected days of the event of a year. Filters are based on the `stored` timings.
+    pub fn resolve(&self, year: Year, stored: &Timings) -> HashSet<Day> {
+        all_days(year)
+            .filter(|day| self.days.as_ref().is_none_or(|days| days.0.contains(day)))
+            .filter(|day| !self.only_incomplete || !stored.is_day_complete(year, *day))
+            .filter(|day| {
+                self.slower_than.is_none_or(|threshold| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let threshold = threshold.as_nanos() as f64;
+                    // NOTE: when a single part is selected, only its own duration is compared.
+                    stored.data.iter().filter(|t| t.day == *day).any(|t| {
+                        let nanos = match self.part {
+                            Some(part) => t.part(part).map_or(0.0, |x| x.nanos),
+                            None => t.total_nanos(),
+                        };
+                        nanos > threshold
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parse a part number, either 1 or 2.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part `{s}`, expecting 1 or 2")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |s: &str| {
+            Day::from_str(s.trim()).map_err(|e: DayFromStrError| DaySetFromStrError(e.to_string()))
+        };
+
+        let mut days = HashSet::new();
+
+        for item in s.split(',') {
+            if let Some((start, end)) = item.split_once("..=") {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                days.extend(AllDays::new().filter(|day| *day >= start && *day <= end));
+            } else if let Some((start, end)) = item.split_once("..") {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                days.extend(AllDays::new().filter(|day| *day >= start && *day < end));
+            } else {
+                days.insert(parse_day(item)?);
+            }
+        }
+
+        Ok(DaySet(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}. Expected days like `6`, `1..=10` or `3,7,12`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use super::{DaySelection, DaySet};
+    use crate::{
+        day,
+        template::{
+            timings::{PartTiming, Timing, Timings},
+            Day,
+        },
+        year,
+    };
+
+    fn days(values: &[u8]) -> HashSet<Day> {
+        values.iter().map(|x| Day::new(*x).unwrap()).collect()
+    }
+
+    fn get_mock_timings() -> Timings {
+        let timing = |day: Day, part_1: f64, part_2: Option<f64>| Timing {
+            part_1: Some(PartTiming::from_nanos(part_1)),
+            part_2: part_2.map(PartTiming::from_nanos),
+            ..Timing::new(day)
+        };
+
+        Timings {
+            data: vec![
+                timing(day!(1), 1_000_000_f64, Some(1_000_000_f64)),
+                timing(day!(2), 1_000_000_f64, None),
+                timing(day!(3), 1_000_000_f64, Some(20_000_000_f64)),
+                timing(day!(4), 20_000_000_f64, Some(1_000_000_f64)),
+            ],
+            history: vec![],
+        }
+    }
+
+    #[test]
+    fn parses_day_sets() {
+        assert_eq!("6".parse::<DaySet>().unwrap().0, days(&[6]));
+        assert_eq!("3,7,12".parse::<DaySet>().unwrap().0, days(&[3, 7, 12]));
+        assert_eq!("1..=3".parse::<DaySet>().unwrap().0, days(&[1, 2, 3]));
+        assert_eq!("1..3".parse::<DaySet>().unwrap().0, days(&[1, 2]));
+        assert_eq!(
+            "1..=2,24..=25".parse::<DaySet>().unwrap().0,
+            days(&[1, 2, 24, 25])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_day_sets() {
+        assert!("".parse::<DaySet>().is_err());
+        assert!("0".parse::<DaySet>().is_err());
+        assert!("1..=26".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn resolves_all_days_by_default() {
+        let selection = DaySelection::default();
+        assert_eq!(
+            selection.resolve(year!(2024), &Timings::default()).len(),
+            25
+        );
+    }
+
+    #[test]
+    fn filters_incomplete_days() {
+        let selection = DaySelection {
+            days: Some("1..=3".parse().unwrap()),
+            only_incomplete: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            selection.resolve(year!(2024), &get_mock_timings()),
+            days(&[2])
+        );
+    }
+
+    #[test]
+    fn filters_slow_days() {
+        let selection = DaySelection {
+            slower_than: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        assert_eq!(
+            selection.resolve(year!(2024), &get_mock_timings()),
+            days(&[3, 4])
+        );
+    }
+
+    #[test]
+    fn filters_slow_parts() {
+        let selection = DaySelection {
+            slower_than: Some(Duration::from_millis(10)),
+            part: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            selection.resolve(year!(2024), &get_mock_timings()),
+            days(&[3])
+        );
+
+        let selection = DaySelection {
+            part: Some(1),
+            ..selection
+        };
+        assert_eq!(
+            selection.resolve(year!(2024), &get_mock_timings()),
+            days(&[4])
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SubmissionVerdict;
+use crate::template::{Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// Represents a single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: SubmissionVerdict,
+}
+
+/// Represents the log of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(SUBMISSIONS_FILE_NAME)
+    }
+
+    /// Dehydrate the submissions of a year to its JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(year.data_dir())?;
+        let mut file = fs::File::create(Submissions::file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the submissions of a year from its JSON file. If not present, returns an empty log.
+    /// A file that can not be read or parsed is an error, so that it is not overwritten by a later store.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = Submissions::file_path(year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Submissions::try_from(s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Add a submission to the log, timestamped with the current time.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: SubmissionVerdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            timestamp,
+            verdict,
+        });
+    }
+
+    /// Find a previous submission of `answer` that was rejected by the server.
+    pub fn find_rejected(&self, day: Day, part: u8, answer: &str) -> Option<&Submission> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.answer == answer && s.verdict.is_wrong())
+    }
+
+    /// Check a numeric answer against the too-high / too-low hints of previous submissions.
+    /// Returns the submission that `answer` contradicts, if any.
+    pub fn find_contradicting_bound(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Option<&Submission> {
+        let value: i128 = answer.trim().parse().ok()?;
+
+        self.data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .find(|s| match (s.verdict, s.answer.trim().parse::<i128>()) {
+                (SubmissionVerdict::TooHigh, Ok(bound)) => value >= bound,
+                (SubmissionVerdict::TooLow, Ok(bound)) => value <= bound,
+                _ => false,
+            })
+    }
+}
+
+/// Format the time passed since `timestamp` in a compact, human-readable way.
+pub fn format_age(timestamp: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    match now.saturating_sub(timestamp) {
+        x if x < 60 => format!("{x}s ago"),
+        x if x < 3600 => format!("{}m ago", x / 60),
+        x if x < 86400 => format!("{}h ago", x / 3600),
+        x => format!("{}d ago", x / 86400),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Name of a verdict in the submission log. The wait time of rate limited submissions is stored separately.
+fn verdict_name(verdict: SubmissionVerdict) -> &'static str {
+    match verdict {
+        SubmissionVerdict::Correct => "correct",
+        SubmissionVerdict::TooHigh => "too high",
+        SubmissionVerdict::TooLow => "too low",
+        SubmissionVerdict::Incorrect => "incorrect",
+        SubmissionVerdict::AlreadySolved => "already solved",
+        SubmissionVerdict::RateLimited(_) => "rate limited",
+        SubmissionVerdict::Unknown => "unknown",
+    }
+}
+
+/// Parse a verdict from its name in the submission log and the wait time stored with it.
+fn parse_verdict(name: &str, wait: Option<Duration>) -> Result<SubmissionVerdict, String> {
+    match name {
+        "correct" => Ok(SubmissionVerdict::Correct),
+        "too high" => Ok(SubmissionVerdict::TooHigh),
+        "too low" => Ok(SubmissionVerdict::TooLow),
+        "incorrect" => Ok(SubmissionVerdict::Incorrect),
+        "already solved" => Ok(SubmissionVerdict::AlreadySolved),
+        "rate limited" => Ok(SubmissionVerdict::RateLimited(wait)),
+        "unknown" => Ok(SubmissionVerdict::Unknown),
+        _ => Err(format!("unknown verdict `{name}`")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_name(value.verdict).into()),
+        );
+
+        if let SubmissionVerdict::RateLimited(Some(wait)) = value.verdict {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("wait".into(), JsonValue::Number(wait.as_secs() as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        // NOTE: only rate limited submissions store the time that was left to wait.
+        let wait = match json.get("wait") {
+            None => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|x| Duration::from_secs_f64(*x))
+                    .ok_or("Expected submission.wait to be a number.")?,
+            ),
+        };
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| parse_verdict(v, wait).ok())
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            timestamp,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use crate::day;
+    use crate::template::aoc_client::SubmissionVerdict;
+
+    use super::Submissions;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(6), 2, "2000", SubmissionVerdict::TooHigh);
+        submissions.record(day!(6), 2, "1000", SubmissionVerdict::TooLow);
+        submissions.record(day!(6), 2, "1500", SubmissionVerdict::Incorrect);
+        submissions.record(day!(6), 2, "1600", SubmissionVerdict::Unknown);
+        submissions.record(
+            day!(6),
+            2,
+            "1700",
+            SubmissionVerdict::RateLimited(Some(Duration::from_secs(46))),
+        );
+        submissions.record(day!(6), 1, "42", SubmissionVerdict::AlreadySolved);
+        submissions
+    }
+
+    mod guards {
+        use super::get_mock_submissions;
+        use crate::day;
+
+        #[test]
+        fn finds_rejected_answers() {
+            let submissions = get_mock_submissions();
+            assert!(submissions.find_rejected(day!(6), 2, "2000").is_some());
+            assert!(submissions.find_rejected(day!(6), 2, "1500").is_some());
+            assert!(submissions.find_rejected(day!(6), 2, "1600").is_none());
+            assert!(submissions.find_rejected(day!(6), 2, "1700").is_none());
+            assert!(submissions.find_rejected(day!(6), 1, "2000").is_none());
+            assert!(submissions.find_rejected(day!(6), 1, "42").is_none());
+        }
+
+        #[test]
+        fn finds_contradicting_bounds() {
+            let submissions = get_mock_submissions();
+            assert_eq!(
+                submissions
+                    .find_contradicting_bound(day!(6), 2, "2500")
+                    .map(|s| s.answer.as_str()),
+                Some("2000")
+            );
+            assert_eq!(
+                submissions
+                    .find_contradicting_bound(day!(6), 2, "999")
+                    .map(|s| s.answer.as_str()),
+                Some("1000")
+            );
+            assert!(submissions
+                .find_contradicting_bound(day!(6), 2, "1200")
+                .is_none());
+            assert!(submissions
+                .find_contradicting_bound(day!(6), 2, "abc")
+                .is_none());
+        }
+    }
+
+    mod serialization {
+        use std::time::Duration;
+
+        use super::get_mock_submissions;
+        use crate::template::aoc_client::SubmissionVerdict;
+        use crate::template::submissions::Submissions;
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn roundtrips_submissions() {
+            let submissions = get_mock_submissions();
+            let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+            let parsed = Submissions::try_from(json).unwrap();
+            assert_eq!(parsed.data, submissions.data);
+            assert_eq!(parsed.data[0].verdict, SubmissionVerdict::TooHigh);
+            assert_eq!(
+                parsed.data[4].verdict,
+                SubmissionVerdict::RateLimited(Some(Duration::from_secs(46)))
+            );
+            assert_eq!(parsed.data[5].verdict, SubmissionVerdict::AlreadySolved);
+        }
+
+        #[test]
+        fn rejects_unknown_verdicts() {
+            let json =
+                r#"{"data":[{"day":"06","part":2,"answer":"1","timestamp":0,"verdict":"maybe"}]}"#;
+            assert!(Submissions::try_from(json.to_string()).is_err());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+/// Per-part time limits for solution binaries run by `all` and `time`.
+use std::{error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::bench::parse_duration;
+use crate::template::selection::parse_part;
+use crate::template::{Day, DayFromStrError};
+
+/// A single `--timeout` value. Applies to all days, a single day or a single part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeoutRule {
+    pub day: Option<Day>,
+    pub part: Option<u8>,
+    pub duration: Duration,
+}
+
+/// Time limits for solution parts. More specific rules take precedence over less specific ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeoutConfig {
+    pub rules: Vec<TimeoutRule>,
+}
+
+impl TimeoutConfig {
+    /// Read all `--timeout` values from command-line arguments.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(TimeoutConfig {
+            rules: args.values_from_str("--timeout")?,
+        })
+    }
+
+    /// Get the time limit for a part of a day, if any.
+    /// The parse step ([`PARSE_PART`](crate::template::report::PARSE_PART)) only has limits for the whole day.
+    pub fn for_part(&self, day: Day, part: u8) -> Option<Duration> {
+        let find = |day: Option<Day>, part: Option<u8>| {
+            self.rules
+                .iter()
+                .rev()
+                .find(|r| r.day == day && r.part == part)
+                .map(|r| r.duration)
+        };
+
+        find(Some(day), Some(part))
+            .or_else(|| find(Some(day), None))
+            .or_else(|| find(None, None))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for TimeoutRule {
+    type Err = TimeoutRuleFromStrError;
+
+    /// Parses values like `30s` (all days), `06=2m` (day 6) or `06:2=2m` (part 2 of day 6).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, duration) = match s.split_once('=') {
+            Some((target, duration)) => (Some(target), duration),
+            None => (None, s),
+        };
+
+        let duration = parse_duration(duration).map_err(TimeoutRuleFromStrError)?;
+
+        let (day, part) = match target {
+            None => (None, None),
+            Some(target) => {
+                let (day, part) = match target.split_once(':') {
+                    Some((day, part)) => (day, Some(part)),
+                    None => (target, None),
+                };
+
+                let day: Day = day
+                    .parse()
+                    .map_err(|e: DayFromStrError| TimeoutRuleFromStrError(e.to_string()))?;
+
+                let part = part
+                    .map(parse_part)
+                    .transpose()
+                    .map_err(TimeoutRuleFromStrError)?;
+
+                (Some(day), part)
+            }
+        };
+
+        Ok(TimeoutRule {
+            day,
+            part,
+            duration,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`TimeoutRule`].
+#[derive(Debug)]
+pub struct TimeoutRuleFromStrError(String);
+
+impl Error for TimeoutRuleFromStrError {}
+
+impl Display for TimeoutRuleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}. Expected a timeout like `30s`, `06=2m` or `06:2=2m`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{TimeoutConfig, TimeoutRule};
+    use crate::day;
+
+    #[test]
+    fn parses_rules() {
+        let rule: TimeoutRule = "30s".parse().unwrap();
+        assert_eq!(rule.day, None);
+        assert_eq!(rule.part, None);
+        assert_eq!(rule.duration, Duration::from_secs(30));
+
+        let rule: TimeoutRule = "6=2m".parse().unwrap();
+        assert_eq!(rule.day, Some(day!(6)));
+        assert_eq!(rule.part, None);
+        assert_eq!(rule.duration, Duration::from_secs(120));
+
+        let rule: TimeoutRule = "06:2=500ms".parse().unwrap();
+        assert_eq!(rule.day, Some(day!(6)));
+        assert_eq!(rule.part, Some(2));
+        assert_eq!(rule.duration, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert!("30".parse::<TimeoutRule>().is_err());
+        assert!("26=1s".parse::<TimeoutRule>().is_err());
+        assert!("06:3=1s".parse::<TimeoutRule>().is_err());
+    }
+
+    #[test]
+    fn prefers_specific_rules() {
+        let config = TimeoutConfig {
+            rules: ["06:2=3s", "10s", "06=2s"]
+                .iter()
+                .map(|x| x.parse().unwrap())
+                .collect(),
+        };
+
+        assert_eq!(config.for_part(day!(6), 2), Some(Duration::from_secs(3)));
+        assert_eq!(config.for_part(day!(6), 1), Some(Duration::from_secs(2)));
+        assert_eq!(config.for_part(day!(7), 1), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn handles_missing_rules() {
+        let config = TimeoutConfig::default();
+        assert_eq!(config.for_part(day!(1), 1), None);
+    }
+}
@@ -1,17 +1,128 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::bench::{
+    parse_duration, stats_from_json, stats_to_json, BenchStats, Statistic,
+};
+use crate::template::memory::{memory_from_json, memory_to_json, MemoryStats};
+use crate::template::report::PARSE_PART;
+use crate::template::{Day, PuzzleId, Year};
+
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the `timings.json` schema written by this template.
+/// Version 1 (files without a `version` key) stored durations as formatted strings and is migrated on read.
+const TIMINGS_VERSION: u8 = 2;
+
+/// Represents the benchmark time of a single part or parse step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub nanos: f64,
+    /// Number of times the part was run. `0` if unknown, e.g. for migrated timings.
+    pub samples: u64,
+    pub stats: Option<BenchStats>,
+    /// Only set for parts that were run with `--memory`.
+    pub memory: Option<MemoryStats>,
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// Only set for solutions with a separate parse step.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// The part that hit its time limit, if any.
+    pub timed_out: Option<u8>,
+}
+
+/// A stored benchmark run, kept to track timings over time.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The git commit the run was benched at, if available.
+    pub commit: Option<String>,
+    /// Timings of the days that were benched in this run.
+    pub data: Vec<Timing>,
+}
+
+impl PartTiming {
+    /// A timing of a single run without statistics.
+    pub fn from_nanos(nanos: f64) -> Self {
+        PartTiming {
+            nanos,
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    /// Get the duration represented by a statistic.
+    /// Falls back to the measured duration for timings recorded without stats.
+    pub fn nanos_by(&self, statistic: Statistic) -> f64 {
+        self.stats
+            .as_ref()
+            .map_or(self.nanos, |stats| stats.get(statistic))
+    }
+}
+
+impl Timing {
+    /// A day without timed parts.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            timed_out: None,
+        }
+    }
+
+    /// Get the timing of a part (or [`PARSE_PART`] for the parse step).
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            PARSE_PART => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Get the duration of a part (or [`PARSE_PART`] for the parse step), represented by a statistic.
+    pub fn part_nanos_by(&self, part: u8, statistic: Statistic) -> Option<f64> {
+        self.part(part).map(|x| x.nanos_by(statistic))
+    }
+
+    /// Get the benchmark statistics of a part (or [`PARSE_PART`] for the parse step), if recorded.
+    pub fn part_stats(&self, part: u8) -> Option<&BenchStats> {
+        self.part(part).and_then(|x| x.stats.as_ref())
+    }
+
+    fn parts(&self) -> impl Iterator<Item = &PartTiming> {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Sum up the measured duration of the parse step and both parts.
+    pub fn total_nanos(&self) -> f64 {
+        self.parts().map(|x| x.nanos).sum()
+    }
+
+    /// Sum up the duration of the parse step and both parts, represented by a statistic.
+    pub fn total_nanos_by(&self, statistic: Statistic) -> f64 {
+        self.parts().map(|x| x.nanos_by(statistic)).sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,22 +130,58 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// Previously stored runs, oldest first.
+    pub history: Vec<HistoryEntry>,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// The JSON file of the timings of a year, e.g. `data/2024/timings.json`.
+    pub fn file_path(year: Year) -> PathBuf {
+        year.data_dir().join(TIMINGS_FILE_NAME)
+    }
+
+    /// Dehydrate the timings of a year to its JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(year.data_dir())?;
+        let mut file = fs::File::create(Timings::file_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from its JSON file. If not present, returns empty timings.
+    /// A file that can not be read or parsed is an error, so that it is not overwritten by a later store.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = Timings::file_path(year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Timings::try_from(s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Rehydrate timings from a JSON file at `path`, e.g. a copy of `data/2024/timings.json`.
+    pub fn read_from_path(path: impl AsRef<Path>) -> Result<Self, String> {
+        fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
-            .unwrap_or_default()
+    }
+
+    /// Find a history entry, either as `~N` for the N-th latest run (`~0` being the latest) or by a prefix of its git commit.
+    /// If several entries match a commit, the latest one is returned.
+    pub fn find_history(&self, reference: &str) -> Option<&HistoryEntry> {
+        match reference.strip_prefix('~') {
+            Some(offset) => {
+                let offset: usize = offset.parse().ok()?;
+                let index = self.history.len().checked_sub(offset + 1)?;
+                self.history.get(index)
+            }
+            None => self.history.iter().rev().find(|entry| {
+                entry
+                    .commit
+                    .as_ref()
+                    .is_some_and(|commit| commit.starts_with(reference))
+            }),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,33 +198,141 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+        Timings {
+            data,
+            history: self.history.clone(),
+        }
+    }
+
+    /// Merge timings of a run that was limited to a single part.
+    /// Unlike [`Timings::merge`], stored timings of the other part are kept.
+    pub fn merge_part(&self, new: &Self, part: u8) -> Self {
+        let data = new
+            .data
+            .iter()
+            .map(|timing| {
+                let Some(stored) = self.data.iter().find(|t| t.day == timing.day) else {
+                    return timing.clone();
+                };
+
+                let mut merged = stored.clone();
+
+                if timing.parse.is_some() {
+                    merged.parse.clone_from(&timing.parse);
+                }
+
+                if part == 1 {
+                    merged.part_1.clone_from(&timing.part_1);
+                } else {
+                    merged.part_2.clone_from(&timing.part_2);
+                }
+
+                merged.timed_out = timing.timed_out;
+                merged
+            })
+            .collect();
+
+        self.merge(&Timings {
+            data,
+            history: vec![],
+        })
+    }
+
+    /// Keep stored memory stats for parts of `self` that were run without measuring memory.
+    pub fn inherit_memory(&mut self, stored: &Self) {
+        for timing in &mut self.data {
+            let Some(stored) = stored.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            let parts = [
+                (&mut timing.parse, &stored.parse),
+                (&mut timing.part_1, &stored.part_1),
+                (&mut timing.part_2, &stored.part_2),
+            ];
+
+            for (part, stored_part) in parts {
+                if let (Some(part), Some(stored_part)) = (part, stored_part) {
+                    if part.memory.is_none() {
+                        part.memory = stored_part.memory;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Append a benchmark run to the history, tagged with the current time and git commit.
+    pub fn record_history(&mut self, run: &Timings) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.history.push(HistoryEntry {
+            timestamp,
+            commit: current_commit(),
+            data: run.data.clone(),
+        });
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    /// Sum up total duration of timings as millis, represented by a statistic.
+    pub fn total_millis_by(&self, statistic: Statistic) -> f64 {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .map(|x| x.total_nanos_by(statistic))
+            .sum::<f64>()
+            / 1_000_000_f64
+    }
+
+    /// Whether every part of a day of the event of `year` is timed.
+    pub fn is_day_complete(&self, year: Year, day: Day) -> bool {
+        let part_count = PuzzleId::new(year, day).part_count();
+
+        self.data
+            .iter()
+            .any(|t| t.day == day && t.part_1.is_some() && (t.part_2.is_some() || part_count == 1))
     }
 }
 
+/// Get the abbreviated hash of the checked out git commit.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,18 +343,48 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: files written before the schema was versioned do not contain this key.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            None => 1,
+            Some(v) => v
+                .get::<f64>()
+                .map(|x| *x as u8)
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings file has version {version}, but only versions up to {TIMINGS_VERSION} are supported."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: older timing files do not contain this key.
+        let json_history = match json.get("history") {
+            None => &vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?,
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|x| Timing::from_json(x, version))
+                .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(|x| HistoryEntry::from_json(x, version))
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -107,40 +392,152 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
-impl From<&Timing> for JsonValue {
-    fn from(value: &Timing) -> Self {
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
 
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
                 None => JsonValue::Null,
             },
         );
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
+impl HistoryEntry {
+    /// Parse a history entry stored with schema `version`.
+    fn from_json(value: &JsonValue, version: u8) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.commit to be null or string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected history.data to be an array.")?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit: commit.cloned(),
+            data: data
+                .iter()
+                .map(|x| Timing::from_json(x, version))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+        map.insert("memory".into(), memory_to_json(value.memory.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
     type Error = String;
 
     fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected part.samples to be a number.")?;
+
+        Ok(PartTiming {
+            nanos,
+            samples,
+            stats: stats_from_json(json, "stats")?,
+            memory: memory_from_json(json, "memory")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Timing> for JsonValue {
+    fn from(value: &Timing) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        map.insert(
+            "timed_out".into(),
+            match value.timed_out {
+                Some(x) => JsonValue::Number(f64::from(x)),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl Timing {
+    /// Parse a timing stored with schema `version`, migrating older versions.
+    fn from_json(value: &JsonValue, version: u8) -> Result<Self, String> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
@@ -151,76 +548,146 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let (parse, part_1, part_2) = if version == 1 {
+            (
+                part_from_v1_json(json, "parse", "parse_stats")?,
+                part_from_v1_json(json, "part_1", "part_1_stats")?,
+                part_from_v1_json(json, "part_2", "part_2_stats")?,
+            )
+        } else {
+            (
+                part_from_json(json, "parse")?,
+                part_from_json(json, "part_1")?,
+                part_from_json(json, "part_2")?,
+            )
+        };
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        // NOTE: older timing files do not contain this key.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timed_out = match json.get("timed_out") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|x| *x as u8)
+                    .ok_or("Expected timing.timed_out to be null or a number.")?,
+            ),
+        };
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            parse,
+            part_1,
+            part_2,
+            timed_out,
         })
     }
 }
 
+/// Read an optional part timing at `key`.
+fn part_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<PartTiming>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => PartTiming::try_from(v).map(Some),
+    }
+}
+
+/// Read an optional part timing at `key` from a version 1 timing, which stored the duration as a formatted string next to its stats.
+fn part_from_v1_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+    stats_key: &str,
+) -> Result<Option<PartTiming>, String> {
+    let duration = match json.get(key) {
+        None => return Ok(None),
+        Some(v) if v.is_null() => return Ok(None),
+        Some(v) => v
+            .get::<String>()
+            .ok_or_else(|| format!("Expected timing.{key} to be null or string."))?,
+    };
+
+    let duration = parse_duration(duration)
+        .map_err(|e| format!("Expected timing.{key} to be a duration: {e}"))?;
+
+    let stats = stats_from_json(json, stats_key)?;
+
+    #[allow(clippy::cast_precision_loss)]
+    Ok(Some(PartTiming {
+        nanos: duration.as_nanos() as f64,
+        samples: stats.as_ref().map_or(0, |x| x.samples),
+        stats,
+        memory: None,
+    }))
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(PartTiming::from_nanos(1e+7)),
+                    part_2: Some(PartTiming::from_nanos(2e+7)),
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(PartTiming::from_nanos(3e+7)),
+                    part_2: Some(PartTiming::from_nanos(4e+7)),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
+                    part_1: Some(PartTiming::from_nanos(4e+7)),
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{day, template::timings::Timings, year};
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 5, "stats": null }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1.as_ref().unwrap().nanos, 1_000_000_f64);
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, 5);
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos(), 1_000_000_f64);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 13, "stats": { "min": 1, "median": 2, "mean": 3, "p95": 4, "stddev": 5, "samples": 6, "outliers": 7 } }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_stats(1).unwrap();
+            assert_eq!(stats.median, 2.0);
+            assert_eq!(stats.outliers, 7);
+            assert_eq!(timing.part_stats(2), None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_timeouts() {
+            let json = r#"{ "version": 2, "data": [{ "day": "06", "part_1": { "nanos": 1000000, "samples": 1, "stats": null }, "part_2": null, "timed_out": 2 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.timed_out, Some(2));
+            assert_eq!(timings.is_day_complete(year!(2024), day!(6)), false);
         }
 
         #[test]
@@ -240,13 +707,71 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": "1ms" }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+    }
+
+    mod migration {
+        use crate::{day, template::timings::Timings};
+
+        #[test]
+        fn migrates_string_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.part_1.as_ref().unwrap().nanos, 1_500_000_f64);
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, 0);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn migrates_string_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_stats": { "min": 1, "median": 2, "mean": 3, "p95": 4, "stddev": 5, "samples": 6, "outliers": 7 }, "part_2_stats": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.samples, 6);
+            assert_eq!(part_1.stats.as_ref().unwrap().median, 2.0);
+        }
+
+        #[test]
+        fn migrates_string_timings_with_parse_step() {
+            let json = r#"{ "data": [{ "day": "10", "parse": "2ms", "part_1": "1ms", "part_2": "1ms", "total_nanos": 4000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.as_ref().unwrap().nanos, 2_000_000_f64);
+            assert_eq!(timing.total_nanos(), 4_000_000_f64);
+            assert_eq!(timings.history.len(), 0);
+        }
+
+        #[test]
+        fn migrates_history_entries() {
+            let json = r#"{ "data": [], "history": [{ "timestamp": 1, "commit": null, "data": [{ "day": "02", "part_1": "3µs", "part_2": null, "total_nanos": 3000 }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.history[0].data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 3_000_f64);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_invalid_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::memory::MemoryStats;
+        use crate::template::timings::{HistoryEntry, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -254,73 +779,146 @@ mod tests {
         fn serializes_timings() {
             let timings = get_mock_timings();
             let value = JsonValue::try_from(timings).unwrap();
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
+                json.get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
                     .unwrap()
                     .len(),
                 3
             );
+            assert_eq!(json.get("version").unwrap().get::<f64>(), Some(&2.0));
+        }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
+
+        #[test]
+        fn roundtrips_memory() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+                peak_bytes: 1024,
+                total_bytes: 4096,
+                allocations: 12,
+            });
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[0].part_2.as_ref().unwrap().memory, None);
+        }
+
+        #[test]
+        fn finds_history_entries() {
+            let mut timings = get_mock_timings();
+            for (timestamp, commit) in [(1, "abc1234"), (2, "def5678"), (3, "abc1234")] {
+                timings.history.push(HistoryEntry {
+                    timestamp,
+                    commit: Some(commit.into()),
+                    data: vec![],
+                });
+            }
+
+            let find = |reference: &str| timings.find_history(reference).map(|x| x.timestamp);
+            assert_eq!(find("~0"), Some(3));
+            assert_eq!(find("~2"), Some(1));
+            assert_eq!(find("~3"), None);
+            assert_eq!(find("def"), Some(2));
+            assert_eq!(find("abc1"), Some(3));
+            assert_eq!(find("fff"), None);
+        }
+
+        #[test]
+        fn roundtrips_history() {
+            let mut timings = get_mock_timings();
+            timings.history.push(HistoryEntry {
+                timestamp: 1_733_000_000,
+                commit: Some("abc1234".into()),
+                data: timings.data[..1].to_vec(),
+            });
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.history.len(), 1);
+            assert_eq!(parsed.history[0].timestamp, 1_733_000_000);
+            assert_eq!(parsed.history[0].commit, Some("abc1234".into()));
+            assert_eq!(parsed.history[0].data[0].total_nanos(), 3e+7);
         }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
+            year,
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    part_1: Some(PartTiming::from_nanos(1e+6)),
+                    part_2: Some(PartTiming::from_nanos(2e+6)),
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    part_1: Some(PartTiming::from_nanos(1e+6)),
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), false);
         }
 
         #[test]
-        fn handles_uncompleted_days() {
+        fn handles_single_part_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    part_1: Some(PartTiming::from_nanos(1e+6)),
+                    ..Timing::new(day!(25))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(25)), true);
+        }
+
+        #[test]
+        fn handles_uncompleted_days() {
+            let timings = Timings {
+                data: vec![Timing::new(day!(1))],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::memory::MemoryStats,
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -329,12 +927,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,19 +943,15 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0_f64);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
@@ -380,5 +970,48 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_other_parts_of_partial_timings() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing {
+                    part_2: Some(PartTiming::from_nanos(1e+7)),
+                    ..Timing::new(day!(2))
+                }],
+                history: vec![],
+            };
+            let merged = timings.merge_part(&other, 2);
+
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[1].part_1, Some(PartTiming::from_nanos(3e+7)));
+            assert_eq!(merged.data[1].part_2, Some(PartTiming::from_nanos(1e+7)));
+            assert_eq!(merged.data[1].total_nanos(), 4e+7);
+        }
+
+        #[test]
+        fn inherits_stored_memory() {
+            let mut stored = get_mock_timings();
+            stored.data[0].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+                peak_bytes: 1024,
+                total_bytes: 2048,
+                allocations: 2,
+            });
+
+            let mut timings = get_mock_timings();
+            timings.data[0].part_2.as_mut().unwrap().memory = Some(MemoryStats::default());
+            timings.inherit_memory(&stored);
+
+            assert_eq!(
+                timings.data[0].part_1.as_ref().unwrap().memory,
+                stored.data[0].part_1.as_ref().unwrap().memory
+            );
+            assert_eq!(
+                timings.data[0].part_2.as_ref().unwrap().memory,
+                Some(MemoryStats::default())
+            );
+            assert_eq!(timings.data[1].part_1.as_ref().unwrap().memory, None);
+        }
     }
 }
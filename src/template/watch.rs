@@ -0,0 +1,237 @@
+/// Detection of changes to the files of a day and to its answers, used by `cargo dev`.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::examples::Examples;
+use crate::template::report::{PartReport, PARSE_PART};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How often watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors often write a file in several steps, wait for them to settle before rerunning.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// The files that affect the solution of a day: its binary, the shared library code, its input and its examples.
+pub fn watched_paths(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let mut paths = vec![
+        puzzle.bin_path(),
+        PathBuf::from("src/lib.rs"),
+        puzzle.input_path(),
+    ];
+
+    // NOTE: the manifest is read on every check, so that newly added examples are picked up.
+    // A manifest that can't be read is still watched, its files are picked up once it is fixed.
+    let examples = Examples::read_from_file(puzzle).unwrap_or_default();
+    paths.extend(example_paths(puzzle, &examples));
+
+    paths
+}
+
+/// The example manifest of a puzzle and every example file it refers to.
+fn example_paths(puzzle: PuzzleId, examples: &Examples) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = examples
+        .data
+        .iter()
+        .map(|example| example.path(puzzle.year))
+        .collect();
+    files.sort();
+    files.dedup();
+
+    let mut paths = vec![Examples::file_path(puzzle)];
+    paths.extend(files);
+    paths
+}
+
+/// Modification times of a set of files. Files that don't exist are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Self {
+        Snapshot(
+            paths
+                .iter()
+                .filter_map(|path| {
+                    let modified = fs::metadata(path).and_then(|x| x.modified()).ok()?;
+                    Some((path.clone(), modified))
+                })
+                .collect(),
+        )
+    }
+
+    /// Paths that were added, removed or modified since `previous`.
+    pub fn changed_paths(&self, previous: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .0
+            .iter()
+            .filter(|(path, modified)| previous.0.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        changed.extend(
+            previous
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .cloned(),
+        );
+
+        changed.sort();
+        changed
+    }
+}
+
+/// Block until a file of `puzzle` changes and return the new snapshot together with the changed paths.
+pub fn wait_for_change(puzzle: PuzzleId, previous: &Snapshot) -> (Snapshot, Vec<PathBuf>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let snapshot = Snapshot::take(&watched_paths(puzzle));
+        if snapshot != *previous {
+            thread::sleep(SETTLE_DELAY);
+            let snapshot = Snapshot::take(&watched_paths(puzzle));
+            let changed = snapshot.changed_paths(previous);
+            return (snapshot, changed);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The answers of both parts of a run. `None` if a part was not solved.
+pub type Answers = [Option<String>; 2];
+
+pub fn answers_from_reports(reports: &[PartReport]) -> Answers {
+    let mut answers: Answers = [None, None];
+
+    for report in reports.iter().filter(|r| r.part != PARSE_PART) {
+        if let Some(answer) = answers.get_mut(usize::from(report.part) - 1) {
+            answer.clone_from(&report.answer);
+        }
+    }
+
+    answers
+}
+
+fn format_answer(answer: Option<&String>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => format!("▼ ({} lines)", answer.lines().count()),
+        Some(answer) => format!("`{answer}`"),
+        None => "✖".into(),
+    }
+}
+
+/// Summarize which answers changed between two runs, one line per part.
+pub fn format_answer_diff(previous: &Answers, current: &Answers) -> String {
+    let lines: Vec<String> = previous
+        .iter()
+        .zip(current)
+        .enumerate()
+        .map(|(index, (old, new))| {
+            let part = index + 1;
+            if old == new {
+                format!("Part {part}: {ANSI_ITALIC}unchanged{ANSI_RESET}")
+            } else {
+                format!(
+                    "Part {part}: {} → {ANSI_BOLD}{}{ANSI_RESET}",
+                    format_answer(old.as_ref()),
+                    format_answer(new.as_ref())
+                )
+            }
+        })
+        .collect();
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        day,
+        template::examples::{Example, Examples},
+        template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET},
+        year,
+    };
+
+    use super::{example_paths, format_answer_diff, Snapshot};
+
+    #[test]
+    fn watches_manifest_and_example_files() {
+        let example = |name: &str, file: &str| Example {
+            name: name.into(),
+            file: file.into(),
+            expected: Default::default(),
+        };
+
+        let examples = Examples {
+            data: vec![
+                example("large", "01-large.txt"),
+                example("example", "01.txt"),
+                example("again", "01.txt"),
+            ],
+        };
+
+        assert_eq!(
+            example_paths(PuzzleId::new(year!(2024), day!(1)), &examples),
+            vec![
+                PathBuf::from("data/2024/examples/01.json"),
+                PathBuf::from("data/2024/examples/01-large.txt"),
+                PathBuf::from("data/2024/examples/01.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_changed_paths() {
+        let time = |secs: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+        let previous = Snapshot(
+            [
+                (PathBuf::from("a"), time(1)),
+                (PathBuf::from("b"), time(1)),
+                (PathBuf::from("c"), time(1)),
+            ]
+            .into(),
+        );
+        let current = Snapshot(
+            [
+                (PathBuf::from("a"), time(1)),
+                (PathBuf::from("b"), time(2)),
+                (PathBuf::from("d"), time(1)),
+            ]
+            .into(),
+        );
+
+        assert_eq!(
+            current.changed_paths(&previous),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+        assert!(current.changed_paths(&current).is_empty());
+    }
+
+    #[test]
+    fn formats_answer_diff() {
+        let previous = [Some("11".to_string()), None];
+        let current = [Some("11".to_string()), Some("a\nb".to_string())];
+
+        assert_eq!(
+            format_answer_diff(&previous, &current),
+            format!(
+                "Part 1: {ANSI_ITALIC}unchanged{ANSI_RESET}\nPart 2: ✖ → {ANSI_BOLD}▼ (2 lines){ANSI_RESET}"
+            )
+        );
+    }
+}